@@ -0,0 +1,71 @@
+//! Compares indexed search with a full scan of every ayah.
+//!
+//! ```text
+//! cargo run --release --example search_benchmark
+//! ```
+
+use std::time::Instant;
+
+use quran::{
+    normalizer::Normalizer,
+    quran::{matching::MatchMode, search::QuranSearch, Quran},
+    remove_diacritics,
+};
+
+const QUERIES: usize = 1000;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let quran = Quran::new()?;
+    let layer = quran.primary_layer().clone();
+    let normalizer = Normalizer::new();
+    let ayahs = quran.ayas();
+
+    // Every 7th word of the text, so that queries mix frequent and rare words.
+    let terms: Vec<String> = ayahs
+        .iter()
+        .flat_map(|ayah| ayah.words())
+        .step_by(7)
+        .take(QUERIES)
+        .map(remove_diacritics)
+        .collect();
+
+    let start = Instant::now();
+    let scanned: usize = terms
+        .iter()
+        .map(|term| {
+            ayahs
+                .iter()
+                .filter(|ayah| ayah.matches_in(term, &layer, MatchMode::Exact, &normalizer))
+                .count()
+        })
+        .sum();
+    let scan_time = start.elapsed();
+
+    let start = Instant::now();
+    let search = QuranSearch::new(&quran);
+    let first = search.search_ayahs(&terms[0]).len();
+    let build_time = start.elapsed();
+
+    let start = Instant::now();
+    let indexed: usize = first
+        + terms[1..]
+            .iter()
+            .map(|term| search.search_ayahs(term).len())
+            .sum::<usize>();
+    let index_time = start.elapsed();
+
+    assert_eq!(scanned, indexed);
+
+    println!("{} queries, {} matching ayahs", terms.len(), indexed);
+    println!("scan:  {:>10.2?}", scan_time);
+    println!(
+        "index: {:>10.2?} (+ {:.2?} to build)",
+        index_time, build_time
+    );
+    println!(
+        "speedup: {:.0}x",
+        scan_time.as_secs_f64() / index_time.as_secs_f64()
+    );
+
+    Ok(())
+}
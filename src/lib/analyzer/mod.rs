@@ -0,0 +1,115 @@
+//! Analysis of translation text for search.
+//!
+//! Each whitespace-separated word becomes at most one term: case folded,
+//! stripped of surrounding punctuation, and optionally dropped as a stop word
+//! or reduced to its stem, so that "Merciful" and "mercy" both give "merci".
+
+use std::sync::LazyLock;
+
+use rust_stemmers::{Algorithm, Stemmer};
+
+static ENGLISH_STEMMER: LazyLock<Stemmer> = LazyLock::new(|| Stemmer::create(Algorithm::English));
+
+const ENGLISH_STOP_WORDS: &[&str] = &[
+    "a", "about", "after", "all", "also", "am", "an", "and", "any", "are", "as", "at", "be",
+    "been", "before", "being", "both", "but", "by", "can", "could", "did", "do", "does", "each",
+    "for", "from", "had", "has", "have", "having", "here", "how", "if", "in", "into", "is", "it",
+    "its", "itself", "just", "may", "more", "most", "no", "nor", "not", "of", "off", "on", "once",
+    "only", "or", "other", "our", "out", "over", "own", "same", "should", "so", "some", "such",
+    "than", "that", "the", "their", "theirs", "them", "then", "there", "these", "they", "this",
+    "those", "through", "to", "too", "under", "until", "up", "upon", "very", "was", "were", "what",
+    "when", "where", "which", "while", "who", "whom", "why", "will", "with", "would",
+];
+
+/// Turns translation text into index terms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextAnalyzer {
+    stemming: bool,
+    stop_words: bool,
+}
+
+impl Default for TextAnalyzer {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+impl TextAnalyzer {
+    /// Case folding, English stop words and Snowball stemming.
+    pub fn english() -> Self {
+        Self {
+            stemming: true,
+            stop_words: true,
+        }
+    }
+
+    /// Case folding only, for languages without a stemmer.
+    pub fn basic() -> Self {
+        Self {
+            stemming: false,
+            stop_words: false,
+        }
+    }
+
+    /// The analyzer for a translation in `language`, a tag such as "en".
+    pub fn for_language(language: &str) -> Self {
+        match language {
+            "en" => Self::english(),
+            _ => Self::basic(),
+        }
+    }
+
+    /// The term of every whitespace-separated word of `text`, in order, with
+    /// `None` for stop words and words without letters or digits.
+    pub fn analyze(&self, text: &str) -> Vec<Option<String>> {
+        text.split_whitespace()
+            .map(|word| self.term(word))
+            .collect()
+    }
+
+    /// The term of a single word.
+    pub fn term(&self, word: &str) -> Option<String> {
+        let word = word
+            .trim_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase();
+        if word.is_empty() || (self.stop_words && ENGLISH_STOP_WORDS.contains(&word.as_str())) {
+            return None;
+        }
+        if self.stemming {
+            Some(ENGLISH_STEMMER.stem(&word).into_owned())
+        } else {
+            Some(word)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze() {
+        let analyzer = TextAnalyzer::english();
+        assert_eq!(
+            analyzer.analyze("[All] praise is due to Allah, Lord of the worlds -"),
+            vec![
+                None,
+                Some("prais".to_string()),
+                None,
+                Some("due".to_string()),
+                None,
+                Some("allah".to_string()),
+                Some("lord".to_string()),
+                None,
+                None,
+                Some("world".to_string()),
+                None,
+            ]
+        );
+        assert_eq!(analyzer.term("Merciful"), analyzer.term("mercy"));
+
+        let basic = TextAnalyzer::basic();
+        assert_eq!(basic.term("The"), Some("the".to_string()));
+        assert_eq!(basic.term("Worlds,"), Some("worlds".to_string()));
+    }
+}
@@ -6,4 +6,80 @@ pub enum QuranError {
     FileOpenError(String),
     #[error("failed to parse JSON: {0}")]
     JsonError(String),
+    /// The JSON is well formed but a value does not fit the expected layout.
+    ///
+    /// `surah` and `verse` are zero-based positions in the surah and verse arrays, and
+    /// `path` is the JSON path of the offending value, e.g. `[1].verses[4].id`.
+    #[error("invalid value at {path}: {message}")]
+    SchemaError {
+        path: String,
+        surah: Option<usize>,
+        verse: Option<usize>,
+        field: Option<String>,
+        message: String,
+    },
+    /// A line- or element-oriented source (Tanzil text/XML, CSV, morphology
+    /// TSV) is malformed; `origin` is where it was read from.
+    #[error("{origin}, line {line}: {message}")]
+    FormatError {
+        origin: String,
+        line: usize,
+        message: String,
+    },
+    #[error("text layer `{0}` is not loaded")]
+    UnknownLayer(String),
+    #[error("text layer `{0}` is the primary text and cannot be attached again")]
+    PrimaryLayer(String),
+    #[error("text layer `{0}` is already attached")]
+    LayerAttached(String),
+    /// An edition attached as a layer does not line up with the loaded text;
+    /// `surah` is `None` when the number of surahs differs.
+    #[error("layer `{layer}` does not match the loaded text: expected {expected}, found {found}")]
+    LayerMismatch {
+        layer: String,
+        surah: Option<u32>,
+        expected: usize,
+        found: usize,
+    },
+    #[error("invalid query: {0}")]
+    InvalidQuery(String),
+    #[error("invalid verse reference: {0}")]
+    InvalidReference(String),
+    /// `surahs` is the number of surahs there are.
+    #[error("surah {surah} is out of range 1-{surahs}")]
+    SurahOutOfRange { surah: u32, surahs: usize },
+    /// `ayahs` is the number of ayahs surah `surah` has.
+    #[error("ayah {surah}:{ayah} is out of range; the surah has {ayahs} ayahs")]
+    AyahOutOfRange { surah: u32, ayah: u32, ayahs: usize },
+    /// `count` is the number of divisions of that kind there are.
+    #[error("{division} {number} is out of range 1-{count}")]
+    DivisionOutOfRange {
+        division: String,
+        number: u32,
+        count: u32,
+    },
+    #[error("no morphology is attached to the text")]
+    MorphologyNotLoaded,
+    #[error("morphology refers to word {surah}:{ayah}:{word}, which is not in the text")]
+    MorphologyMismatch { surah: u32, ayah: u32, word: u32 },
+    /// `surah` is the surah number, `path` the JSON path of its verse count,
+    /// e.g. `[1].total_verses`, and `origin` where the text was read from.
+    #[error("{origin}: surah {surah} declares {expected} verses at {path} but contains {found}")]
+    VerseCountMismatch {
+        origin: String,
+        path: String,
+        surah: u32,
+        expected: usize,
+        found: usize,
+    },
+    /// `surah` is the number of the surah the id belongs to; for a surah id,
+    /// the number expected at that position.
+    #[error("expected id {expected} at {path}, found {found}")]
+    NonContiguousId {
+        path: String,
+        surah: u32,
+        verse: Option<usize>,
+        expected: u32,
+        found: u32,
+    },
 }
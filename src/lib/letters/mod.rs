@@ -0,0 +1,291 @@
+//! Counting the letters of Arabic text.
+//!
+//! Only letters are counted: spaces, harakat, shadda, sukun, tatweel and the
+//! Quranic annotation and pause marks are not. What counts beyond the base
+//! letters depends on the [`LetterPolicy`]. [`LetterStats`] counts each
+//! letter separately.
+
+use std::collections::BTreeMap;
+
+/// The 28 letters of the alphabet in their usual order. The hamza and its
+/// seated forms, ta marbuta and alef maqsura are counted apart from them.
+pub const ALPHABET: [char; 28] = [
+    'ا', 'ب', 'ت', 'ث', 'ج', 'ح', 'خ', 'د', 'ذ', 'ر', 'ز', 'س', 'ش', 'ص', 'ض', 'ط', 'ظ', 'ع', 'غ',
+    'ف', 'ق', 'ك', 'ل', 'م', 'ن', 'ه', 'و', 'ي',
+];
+
+/// Which characters count as letters.
+///
+/// [`LetterPolicy::new`] counts the written base letters, alef wasla and the
+/// hamza on the line, but not the dagger alef or the small waw and ya, and a
+/// shadda does not double its letter:
+///
+/// ```
+/// use quran::letters::LetterPolicy;
+///
+/// let policy = LetterPolicy::new();
+/// assert_eq!(policy.count("ٱلرَّحۡمَٰنِ"), 6);
+/// assert_eq!(policy.dagger_alef(true).shadda_doubles(true).count("ٱلرَّحۡمَٰنِ"), 8);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LetterPolicy {
+    dagger_alef: bool,
+    small_waw_ya: bool,
+    hamza_on_line: bool,
+    shadda_doubles: bool,
+}
+
+impl Default for LetterPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LetterPolicy {
+    pub fn new() -> Self {
+        Self {
+            dagger_alef: false,
+            small_waw_ya: false,
+            hamza_on_line: true,
+            shadda_doubles: false,
+        }
+    }
+
+    /// Everything that can be pronounced as a letter: the dagger alef, small
+    /// waw and ya, the hamza on the line, and doubled letters under shadda.
+    pub fn spoken() -> Self {
+        Self {
+            dagger_alef: true,
+            small_waw_ya: true,
+            hamza_on_line: true,
+            shadda_doubles: true,
+        }
+    }
+
+    /// Counts the dagger (superscript) alef U+0670 as an alef.
+    pub fn dagger_alef(mut self, enabled: bool) -> Self {
+        self.dagger_alef = enabled;
+        self
+    }
+
+    /// Counts the small waw U+06E5 and the small ya U+06E6 and U+06E7 as a
+    /// waw and a ya.
+    pub fn small_waw_ya(mut self, enabled: bool) -> Self {
+        self.small_waw_ya = enabled;
+        self
+    }
+
+    /// Counts the hamza written on the line, ء, as a letter. A hamza on a
+    /// seat is counted with its seat either way.
+    pub fn hamza_on_line(mut self, enabled: bool) -> Self {
+        self.hamza_on_line = enabled;
+        self
+    }
+
+    /// Counts a letter with shadda twice.
+    pub fn shadda_doubles(mut self, enabled: bool) -> Self {
+        self.shadda_doubles = enabled;
+        self
+    }
+
+    /// Number of letters in `text`.
+    pub fn count(&self, text: &str) -> usize {
+        self.letters(text).count()
+    }
+
+    /// The letters of `text` in order, each as the letter it counts as: the
+    /// dagger alef and alef wasla are yielded as ا, the small waw and ya as و
+    /// and ي, a hamza on a tatweel as ئ, and a letter under shadda twice when
+    /// shadda doubles.
+    pub fn letters<'a>(&self, text: &'a str) -> impl Iterator<Item = char> + 'a {
+        let policy = *self;
+        let mut previous: Option<char> = None;
+        let mut tatweel = false;
+        text.chars().filter_map(move |c| {
+            let letter = match c {
+                'ـ' => {
+                    tatweel = true;
+                    return None;
+                }
+                '\u{0654}' if tatweel => Some('ئ'),
+                '\u{0651}' if policy.shadda_doubles => previous.take(),
+                '\u{0670}' if policy.dagger_alef => Some('ا'),
+                '\u{06E5}' if policy.small_waw_ya => Some('و'),
+                '\u{06E6}' | '\u{06E7}' if policy.small_waw_ya => Some('ي'),
+                'ء' if policy.hamza_on_line => Some('ء'),
+                'ٱ' => Some('ا'),
+                c if is_base_letter(c) => Some(c),
+                c if is_mark(c) => return None,
+                _ => None,
+            };
+            if c != '\u{0651}' {
+                previous = letter;
+            }
+            tatweel = false;
+            letter
+        })
+    }
+}
+
+/// How often each letter occurs in some text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LetterStats {
+    counts: BTreeMap<char, usize>,
+    total: usize,
+}
+
+impl LetterStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The letters of `text` under `policy`.
+    pub fn of(text: &str, policy: &LetterPolicy) -> Self {
+        let mut stats = Self::new();
+        stats.add(text, policy);
+        stats
+    }
+
+    /// Adds the letters of `text` under `policy`, as yielded by
+    /// [`LetterPolicy::letters`].
+    pub fn add(&mut self, text: &str, policy: &LetterPolicy) {
+        for letter in policy.letters(text) {
+            *self.counts.entry(letter).or_default() += 1;
+            self.total += 1;
+        }
+    }
+
+    /// Adds the counts of `other`.
+    pub fn merge(&mut self, other: &LetterStats) {
+        for (&letter, &count) in &other.counts {
+            *self.counts.entry(letter).or_default() += count;
+        }
+        self.total += other.total;
+    }
+
+    /// Number of letters counted.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    pub fn count(&self, letter: char) -> usize {
+        self.counts.get(&letter).copied().unwrap_or_default()
+    }
+
+    /// Share of `letter` among all letters counted, from 0 to 1.
+    pub fn frequency(&self, letter: char) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.count(letter) as f64 / self.total as f64
+    }
+
+    /// How many times more frequent `letter` is here than in `other`, or
+    /// `None` when it does not occur in `other`.
+    pub fn relative_to(&self, other: &LetterStats, letter: char) -> Option<f64> {
+        let base = other.frequency(letter);
+        (base > 0.0).then(|| self.frequency(letter) / base)
+    }
+
+    /// Every letter that occurs, with its count, in code point order.
+    pub fn counts(&self) -> impl Iterator<Item = (char, usize)> + '_ {
+        self.counts.iter().map(|(&letter, &count)| (letter, count))
+    }
+
+    /// The count of each letter of [`ALPHABET`], in order, zeros included.
+    pub fn alphabet(&self) -> [(char, usize); 28] {
+        ALPHABET.map(|letter| (letter, self.count(letter)))
+    }
+}
+
+/// A letter other than ء, ٱ and the small letters.
+fn is_base_letter(c: char) -> bool {
+    match c {
+        '\u{0600}'..='\u{06FF}' => matches!(
+            c,
+            '\u{0622}'..='\u{063F}'
+                | '\u{0641}'..='\u{064A}'
+                | '\u{066E}'..='\u{066F}'
+                | '\u{0672}'..='\u{06D3}'
+                | '\u{06D5}'
+                | '\u{06EE}'..='\u{06EF}'
+                | '\u{06FA}'..='\u{06FC}'
+                | '\u{06FF}'
+        ),
+        c => c.is_alphabetic() && !is_mark(c),
+    }
+}
+
+/// A combining mark, which belongs to the letter before it. Marks do not
+/// interrupt a letter and its shadda.
+fn is_mark(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0610}'..='\u{061A}'
+            | '\u{064B}'..='\u{065F}'
+            | '\u{0670}'
+            | '\u{06D6}'..='\u{06DC}'
+            | '\u{06DF}'..='\u{06E8}'
+            | '\u{06EA}'..='\u{06ED}'
+            | '\u{0300}'..='\u{036F}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count() {
+        let policy = LetterPolicy::new();
+        // Spaces, harakat, sukun, shadda and the ayah's pause mark are not
+        // letters.
+        assert_eq!(policy.count("بِسۡمِ ٱللَّهِ ٱلرَّحۡمَٰنِ ٱلرَّحِيمِ"), 19);
+        assert_eq!(policy.count("ذَٰلِكَ ٱلۡكِتَٰبُ لَا رَيۡبَۛ فِيهِۛ"), 16);
+        assert_eq!(policy.count("The Opener"), 9);
+
+        assert_eq!(policy.dagger_alef(true).count("ذَٰلِكَ ٱلۡكِتَٰبُ"), 10);
+        assert_eq!(policy.shadda_doubles(true).count("ٱللَّهِ"), 5);
+        assert_eq!(policy.hamza_on_line(false).count("ٱلسَّمَآءِ"), 5);
+        assert_eq!(policy.count("ٱلسَّمَآءِ"), 6);
+        // The small waw of the pronoun.
+        assert_eq!(policy.count("مَالَهُۥ"), 4);
+        assert_eq!(policy.small_waw_ya(true).count("مَالَهُۥ"), 5);
+        // The hamza on a tatweel is a seated hamza.
+        assert_eq!(policy.count("أَنۢبِـُٔونِي"), 7);
+        assert_eq!(policy.count("يَـٰٓـَٔادَمُ"), 5);
+        assert_eq!(policy.dagger_alef(true).count("يَـٰٓـَٔادَمُ"), 6);
+
+        let spoken = LetterPolicy::spoken();
+        assert_eq!(
+            spoken.letters("رَبِّ ٱلۡعَٰلَمِينَ").collect::<String>(),
+            "رببالعالمين"
+        );
+    }
+
+    #[test]
+    fn test_letter_stats() {
+        let policy = LetterPolicy::new();
+        let mut stats = LetterStats::of("ٱلۡحَمۡدُ لِلَّهِ رَبِّ ٱلۡعَٰلَمِينَ", &policy);
+        assert_eq!(stats.total(), 17);
+        assert_eq!(stats.count('ل'), 5);
+        assert_eq!(stats.count('ٱ'), 0);
+        assert_eq!(stats.count('ا'), 2);
+        assert!((stats.frequency('ل') - 5.0 / 17.0).abs() < 1e-9);
+        assert_eq!(stats.alphabet()[0], ('ا', 2));
+        assert_eq!(stats.alphabet()[3], ('ث', 0));
+
+        let other = LetterStats::of("قُلۡ هُوَ ٱللَّهُ أَحَدٌ", &policy);
+        assert_eq!(other.count('أ'), 1);
+        assert_eq!(stats.relative_to(&other, 'ب'), None);
+        assert_eq!(other.relative_to(&other, 'ل'), Some(1.0));
+
+        stats.merge(&other);
+        assert_eq!(stats.total(), 28);
+        assert_eq!(stats.count('ل'), 8);
+        assert_eq!(
+            stats.counts().map(|(_, count)| count).sum::<usize>(),
+            stats.total()
+        );
+    }
+}
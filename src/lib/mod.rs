@@ -1,6 +1,12 @@
+pub mod analyzer;
 pub mod error;
+pub mod letters;
+pub mod morphology;
+pub mod normalizer;
 pub mod quran;
-mod traits;
+pub mod roots;
+pub mod tokenizer;
+pub mod traits;
 mod util;
 
 pub use util::remove_diacritics;
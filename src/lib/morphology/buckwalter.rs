@@ -0,0 +1,89 @@
+//! The extended Buckwalter transliteration used by the Quranic Arabic Corpus.
+
+/// Converts Buckwalter transliteration to Arabic script. Characters without a
+/// mapping are kept as they are.
+pub fn to_arabic(text: &str) -> String {
+    text.chars().map(|c| letter(c).unwrap_or(c)).collect()
+}
+
+fn letter(c: char) -> Option<char> {
+    Some(match c {
+        '\'' => 'ء',
+        '>' => 'أ',
+        '&' => 'ؤ',
+        '<' => 'إ',
+        '}' => 'ئ',
+        'A' => 'ا',
+        'b' => 'ب',
+        'p' => 'ة',
+        't' => 'ت',
+        'v' => 'ث',
+        'j' => 'ج',
+        'H' => 'ح',
+        'x' => 'خ',
+        'd' => 'د',
+        '*' => 'ذ',
+        'r' => 'ر',
+        'z' => 'ز',
+        's' => 'س',
+        '$' => 'ش',
+        'S' => 'ص',
+        'D' => 'ض',
+        'T' => 'ط',
+        'Z' => 'ظ',
+        'E' => 'ع',
+        'g' => 'غ',
+        '_' => 'ـ',
+        'f' => 'ف',
+        'q' => 'ق',
+        'k' => 'ك',
+        'l' => 'ل',
+        'm' => 'م',
+        'n' => 'ن',
+        'h' => 'ه',
+        'w' => 'و',
+        'Y' => 'ى',
+        'y' => 'ي',
+        'F' => '\u{064B}',
+        'N' => '\u{064C}',
+        'K' => '\u{064D}',
+        'a' => '\u{064E}',
+        'u' => '\u{064F}',
+        'i' => '\u{0650}',
+        '~' => '\u{0651}',
+        'o' => '\u{0652}',
+        '^' => '\u{0653}',
+        '#' => '\u{0654}',
+        '`' => '\u{0670}',
+        '{' => 'ٱ',
+        ':' => '\u{06DC}',
+        '@' => '\u{06DF}',
+        '"' => '\u{06E0}',
+        '[' => '\u{06E2}',
+        ';' => '\u{06E3}',
+        ',' => '\u{06E5}',
+        '.' => '\u{06E6}',
+        '!' => '\u{06E8}',
+        '-' => '\u{06EA}',
+        '+' => '\u{06EB}',
+        '%' => '\u{06EC}',
+        ']' => '\u{06ED}',
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_arabic() {
+        assert_eq!(to_arabic("qwl"), "قول");
+        assert_eq!(to_arabic("ktb"), "كتب");
+        assert_eq!(to_arabic("'mn"), "ءمن");
+        assert_eq!(
+            to_arabic("{ll~ah"),
+            "\u{0671}\u{0644}\u{0644}\u{0651}\u{064E}\u{0647}"
+        );
+    }
+}
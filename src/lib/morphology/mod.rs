@@ -0,0 +1,363 @@
+//! The Quranic Arabic Corpus morphology file as an annotation layer.
+//!
+//! The corpus distributes one tab-separated line per word segment:
+//!
+//! ```text
+//! (2:7:1:1)    xatama    V    STEM|POS:V|PERF|LEM:xatama|ROOT:xtm|3MS
+//! ```
+//!
+//! Locations are `(sura:aya:word:segment)`, all 1-based, where `word` counts
+//! the whitespace-separated words of the Uthmani text. Forms, lemmas and roots
+//! are given in Buckwalter transliteration and are converted to Arabic here.
+
+pub mod buckwalter;
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use crate::{error::QuranError, quran::surah::RevelationType, remove_diacritics, roots::Root};
+
+/// Position of a word in the text, 1-based as in the corpus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Location {
+    pub surah: u32,
+    pub ayah: u32,
+    pub word: u32,
+}
+
+/// Verb aspect, from the `PERF`, `IMPF` and `IMPV` features.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aspect {
+    Perfect,
+    Imperfect,
+    Imperative,
+}
+
+/// One morphological segment of a word: a prefix, the stem or a suffix.
+#[derive(Debug, Clone)]
+pub struct Segment {
+    /// The segment in Arabic script.
+    pub form: String,
+    /// Part-of-speech tag, e.g. `N`, `V`, `P`, `PRON`.
+    pub tag: String,
+    /// Raw features, e.g. `["STEM", "POS:V", "IMPF", "LEM:qAla", "ROOT:qwl", "3MS"]`.
+    pub features: Vec<String>,
+    pub root: Option<Root>,
+    /// The lemma in Arabic script.
+    pub lemma: Option<String>,
+}
+
+impl Segment {
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features.iter().any(|f| f == feature)
+    }
+
+    pub fn is_stem(&self) -> bool {
+        self.has_feature("STEM")
+    }
+}
+
+/// The segments of one word.
+#[derive(Debug, Clone)]
+pub struct MorphWord {
+    pub location: Location,
+    pub segments: Vec<Segment>,
+}
+
+impl MorphWord {
+    /// The whole word in Arabic script.
+    pub fn form(&self) -> String {
+        self.segments.iter().map(|s| s.form.as_str()).collect()
+    }
+
+    pub fn stem(&self) -> Option<&Segment> {
+        self.segments.iter().find(|s| s.is_stem())
+    }
+
+    pub fn root(&self) -> Option<&Root> {
+        self.segments.iter().find_map(|s| s.root.as_ref())
+    }
+
+    pub fn lemma(&self) -> Option<&str> {
+        self.segments.iter().find_map(|s| s.lemma.as_deref())
+    }
+
+    /// The part-of-speech tag of the stem.
+    pub fn pos(&self) -> Option<&str> {
+        self.stem().map(|s| s.tag.as_str())
+    }
+
+    pub fn aspect(&self) -> Option<Aspect> {
+        let stem = self.stem()?;
+        if stem.has_feature("PERF") {
+            Some(Aspect::Perfect)
+        } else if stem.has_feature("IMPF") {
+            Some(Aspect::Imperfect)
+        } else if stem.has_feature("IMPV") {
+            Some(Aspect::Imperative)
+        } else {
+            None
+        }
+    }
+
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.segments.iter().any(|s| s.has_feature(feature))
+    }
+}
+
+/// The morphology of the whole text, keyed by word location.
+#[derive(Debug, Clone, Default)]
+pub struct Morphology {
+    words: BTreeMap<Location, MorphWord>,
+}
+
+impl Morphology {
+    /// Parses the corpus TSV. Comment lines starting with `#` and the
+    /// `LOCATION` header are skipped.
+    pub fn parse(input: &str) -> Result<Self, QuranError> {
+        Self::parse_from(input, "<string>")
+    }
+
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, QuranError> {
+        let path = path.as_ref();
+        let input = fs::read_to_string(path)
+            .map_err(|e| QuranError::FileOpenError(format!("{}: {}", path.display(), e)))?;
+        Self::parse_from(&input, &path.display().to_string())
+    }
+
+    /// [`Morphology::parse`], labelling errors with `origin`.
+    fn parse_from(input: &str, origin: &str) -> Result<Self, QuranError> {
+        let mut words: BTreeMap<Location, MorphWord> = BTreeMap::new();
+
+        for (index, line) in input.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty() || line.starts_with('#') || line.starts_with("LOCATION") {
+                continue;
+            }
+
+            let format_error = |message: String| QuranError::FormatError {
+                origin: origin.to_string(),
+                line: line_number,
+                message,
+            };
+
+            let mut columns = line.split('\t');
+            let (location, form, tag, features) = match (
+                columns.next(),
+                columns.next(),
+                columns.next(),
+                columns.next(),
+            ) {
+                (Some(location), Some(form), Some(tag), Some(features)) => {
+                    (location, form, tag, features)
+                }
+                _ => return Err(format_error("expected 4 tab-separated columns".to_string())),
+            };
+
+            let location = parse_location(location)
+                .ok_or_else(|| format_error(format!("invalid location `{}`", location)))?;
+            let features: Vec<String> = features.split('|').map(str::to_string).collect();
+            let root = feature_value(&features, "ROOT:")
+                .map(|root| buckwalter::to_arabic(root).parse::<Root>())
+                .transpose()
+                .map_err(format_error)?;
+            let lemma = feature_value(&features, "LEM:").map(buckwalter::to_arabic);
+
+            words
+                .entry(location)
+                .or_insert_with(|| MorphWord {
+                    location,
+                    segments: Vec::new(),
+                })
+                .segments
+                .push(Segment {
+                    form: buckwalter::to_arabic(form),
+                    tag: tag.to_string(),
+                    features,
+                    root,
+                    lemma,
+                });
+        }
+
+        Ok(Self { words })
+    }
+
+    pub fn word(&self, surah: u32, ayah: u32, word: u32) -> Option<&MorphWord> {
+        self.words.get(&Location { surah, ayah, word })
+    }
+
+    /// The annotated words of one ayah, in order.
+    pub fn ayah_words(&self, surah: u32, ayah: u32) -> impl Iterator<Item = &MorphWord> {
+        let start = Location {
+            surah,
+            ayah,
+            word: 0,
+        };
+        let end = Location {
+            surah,
+            ayah,
+            word: u32::MAX,
+        };
+        self.words.range(start..=end).map(|(_, word)| word)
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = &MorphWord> {
+        self.words.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+/// Criteria for [`QuranSearch::search_morphology`](crate::quran::search::QuranSearch::search_morphology).
+/// Every criterion that is set must hold for the same word.
+#[derive(Debug, Clone, Default)]
+pub struct MorphologyQuery {
+    root: Option<Root>,
+    lemma: Option<String>,
+    pos: Option<String>,
+    aspect: Option<Aspect>,
+    features: Vec<String>,
+    revelation_type: Option<RevelationType>,
+}
+
+impl MorphologyQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts "ق-و-ل" or "قول".
+    pub fn root(mut self, root: &str) -> Result<Self, QuranError> {
+        self.root = Some(root.parse().map_err(QuranError::InvalidQuery)?);
+        Ok(self)
+    }
+
+    /// Matches the lemma regardless of diacritics.
+    pub fn lemma(mut self, lemma: &str) -> Self {
+        self.lemma = Some(remove_diacritics(lemma));
+        self
+    }
+
+    /// Matches the part-of-speech tag of the stem, e.g. `V` or `N`.
+    pub fn pos(mut self, tag: &str) -> Self {
+        self.pos = Some(tag.to_string());
+        self
+    }
+
+    pub fn aspect(mut self, aspect: Aspect) -> Self {
+        self.aspect = Some(aspect);
+        self
+    }
+
+    /// Requires a raw corpus feature on any segment, e.g. `PASS` or `3MP`.
+    pub fn feature(mut self, feature: &str) -> Self {
+        self.features.push(feature.to_string());
+        self
+    }
+
+    /// Restricts matches to Meccan or Medinan surahs.
+    pub fn revelation_type(mut self, revelation_type: RevelationType) -> Self {
+        self.revelation_type = Some(revelation_type);
+        self
+    }
+
+    pub fn revelation_type_filter(&self) -> Option<RevelationType> {
+        self.revelation_type
+    }
+
+    /// Whether `word` satisfies every word-level criterion.
+    pub fn matches(&self, word: &MorphWord) -> bool {
+        self.root
+            .as_ref()
+            .is_none_or(|root| word.root() == Some(root))
+            && self
+                .lemma
+                .as_ref()
+                .is_none_or(|lemma| word.lemma().is_some_and(|l| remove_diacritics(l) == *lemma))
+            && self.pos.as_ref().is_none_or(|pos| word.pos() == Some(pos))
+            && self
+                .aspect
+                .is_none_or(|aspect| word.aspect() == Some(aspect))
+            && self
+                .features
+                .iter()
+                .all(|feature| word.has_feature(feature))
+    }
+}
+
+fn parse_location(location: &str) -> Option<Location> {
+    let mut parts = location
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .split(':')
+        .map(|part| part.parse::<u32>().ok().filter(|&number| number > 0));
+    let surah = parts.next()??;
+    let ayah = parts.next()??;
+    let word = parts.next()??;
+    Some(Location { surah, ayah, word })
+}
+
+fn feature_value<'a>(features: &'a [String], prefix: &str) -> Option<&'a str> {
+    features
+        .iter()
+        .find_map(|feature| feature.strip_prefix(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+# Quranic Arabic Corpus (sample)
+LOCATION\tFORM\tTAG\tFEATURES
+(2:8:3:1)\tyaqu\tV\tSTEM|POS:V|IMPF|LEM:qaAla|ROOT:qwl|3MS
+(2:8:3:2)\twlu\tV\tSUFFIX|IMPF
+(2:11:2:1)\tqiyla\tV\tSTEM|POS:V|PERF|PASS|LEM:qaAla|ROOT:qwl|3MS
+(2:11:3:1)\tla\tP\tPREFIX|l:P+
+(2:11:3:2)\thumo\tPRON\tSUFFIX|PRON:3MP
+";
+
+    #[test]
+    fn test_parse() {
+        let morphology = Morphology::parse(SAMPLE).unwrap();
+        assert_eq!(morphology.len(), 3);
+
+        let word = morphology.word(2, 8, 3).unwrap();
+        assert_eq!(word.segments.len(), 2);
+        assert_eq!(word.root().unwrap().to_string(), "ق-و-ل");
+        assert_eq!(word.aspect(), Some(Aspect::Imperfect));
+        assert_eq!(word.pos(), Some("V"));
+
+        assert_eq!(morphology.ayah_words(2, 11).count(), 2);
+    }
+
+    #[test]
+    fn test_query() {
+        let morphology = Morphology::parse(SAMPLE).unwrap();
+        let query = MorphologyQuery::new()
+            .root("قول")
+            .unwrap()
+            .aspect(Aspect::Imperfect);
+
+        let matches: Vec<_> = morphology.words().filter(|w| query.matches(w)).collect();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].location.ayah, 8);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!(matches!(
+            Morphology::parse("(1:1)\tbi\tP\tPREFIX"),
+            Err(QuranError::FormatError { line: 1, .. })
+        ));
+        assert!(matches!(
+            Morphology::parse("(2:8:0:1)\tyaqu\tV\tSTEM"),
+            Err(QuranError::FormatError { line: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,321 @@
+//! Configurable normalization of Arabic text for matching.
+//!
+//! Every step is an independent toggle. The toggles are compiled into a single
+//! lookup table over the Arabic block, so normalizing costs one table read per
+//! character.
+
+use std::{
+    hash::{Hash, Hasher},
+    ops::Range,
+    sync::LazyLock,
+};
+
+const ARABIC_BLOCK: u32 = 0x0600;
+const TABLE_SIZE: usize = 0x100;
+
+static DEFAULT: LazyLock<Normalizer> = LazyLock::new(Normalizer::new);
+
+/// What happens to one character of the Arabic block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Fold {
+    Keep,
+    Drop,
+    Into(char),
+}
+
+/// The enabled normalization steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Steps {
+    harakat: bool,
+    tatweel: bool,
+    alef_wasla: bool,
+    alef_variants: bool,
+    alef_maqsura: bool,
+    ta_marbuta: bool,
+    hamza_seats: bool,
+    quranic_marks: bool,
+    digits: bool,
+    punctuation: bool,
+}
+
+/// Folds Arabic text to a canonical form before comparing it.
+///
+/// [`Normalizer::new`] reproduces [`remove_diacritics`](crate::remove_diacritics):
+/// harakat, Quranic marks, Arabic-Indic digits and punctuation are dropped and
+/// alef wasla becomes a plain alef. Letter folding is off until enabled:
+///
+/// ```
+/// use quran::normalizer::Normalizer;
+///
+/// let normalizer = Normalizer::new().alef_variants(true).ta_marbuta(true);
+/// assert_eq!(normalizer.normalize("ٱلۡآخِرَةِ"), "الاخره");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Normalizer {
+    steps: Steps,
+    table: [Fold; TABLE_SIZE],
+}
+
+impl PartialEq for Normalizer {
+    fn eq(&self, other: &Self) -> bool {
+        self.steps == other.steps
+    }
+}
+
+impl Eq for Normalizer {}
+
+impl Hash for Normalizer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.steps.hash(state);
+    }
+}
+
+impl Default for Normalizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Normalizer {
+    pub fn new() -> Self {
+        Self::with_steps(Steps {
+            harakat: true,
+            tatweel: false,
+            alef_wasla: true,
+            alef_variants: false,
+            alef_maqsura: false,
+            ta_marbuta: false,
+            hamza_seats: false,
+            quranic_marks: true,
+            digits: true,
+            punctuation: true,
+        })
+    }
+
+    /// A normalizer that leaves text unchanged.
+    pub fn none() -> Self {
+        Self::with_steps(Steps {
+            harakat: false,
+            tatweel: false,
+            alef_wasla: false,
+            alef_variants: false,
+            alef_maqsura: false,
+            ta_marbuta: false,
+            hamza_seats: false,
+            quranic_marks: false,
+            digits: false,
+            punctuation: false,
+        })
+    }
+
+    /// Every step enabled: the most forgiving match.
+    pub fn full() -> Self {
+        Self::with_steps(Steps {
+            harakat: true,
+            tatweel: true,
+            alef_wasla: true,
+            alef_variants: true,
+            alef_maqsura: true,
+            ta_marbuta: true,
+            hamza_seats: true,
+            quranic_marks: true,
+            digits: true,
+            punctuation: true,
+        })
+    }
+
+    /// Drops tanween, the short vowels, shadda, sukun, the dagger alef and the
+    /// other combining marks of U+064B–U+065F.
+    pub fn harakat(mut self, enabled: bool) -> Self {
+        self.steps.harakat = enabled;
+        self.rebuild()
+    }
+
+    /// Drops the tatweel (ـ) used to stretch words.
+    pub fn tatweel(mut self, enabled: bool) -> Self {
+        self.steps.tatweel = enabled;
+        self.rebuild()
+    }
+
+    /// Writes alef wasla (ٱ) as ا.
+    pub fn alef_wasla(mut self, enabled: bool) -> Self {
+        self.steps.alef_wasla = enabled;
+        self.rebuild()
+    }
+
+    /// Writes أ, إ, آ and ٱ as ا.
+    pub fn alef_variants(mut self, enabled: bool) -> Self {
+        self.steps.alef_variants = enabled;
+        self.rebuild()
+    }
+
+    /// Writes alef maqsura (ى) as ي.
+    pub fn alef_maqsura(mut self, enabled: bool) -> Self {
+        self.steps.alef_maqsura = enabled;
+        self.rebuild()
+    }
+
+    /// Writes ta marbuta (ة) as ه.
+    pub fn ta_marbuta(mut self, enabled: bool) -> Self {
+        self.steps.ta_marbuta = enabled;
+        self.rebuild()
+    }
+
+    /// Writes ؤ and ئ as ء, and أ and إ as well unless alef variants are
+    /// folded into ا.
+    pub fn hamza_seats(mut self, enabled: bool) -> Self {
+        self.steps.hamza_seats = enabled;
+        self.rebuild()
+    }
+
+    /// Drops the small Quranic annotation marks of U+06D6–U+06ED. The ayah
+    /// end, ۞ and ۩ are symbols rather than marks and are kept.
+    pub fn quranic_marks(mut self, enabled: bool) -> Self {
+        self.steps.quranic_marks = enabled;
+        self.rebuild()
+    }
+
+    /// Drops Arabic-Indic and Eastern Arabic-Indic digits.
+    pub fn digits(mut self, enabled: bool) -> Self {
+        self.steps.digits = enabled;
+        self.rebuild()
+    }
+
+    /// Drops the Arabic comma, semicolon and question mark.
+    pub fn punctuation(mut self, enabled: bool) -> Self {
+        self.steps.punctuation = enabled;
+        self.rebuild()
+    }
+
+    pub fn normalize(&self, text: &str) -> String {
+        let mut output = String::with_capacity(text.len());
+        for c in text.chars() {
+            match self.fold(c) {
+                Fold::Keep => output.push(c),
+                Fold::Into(replacement) => output.push(replacement),
+                Fold::Drop => {}
+            }
+        }
+        output
+    }
+
+    /// The bytes of `text` that normalize to the characters `chars` of its
+    /// normalized form, with the dropped marks that follow the last of them.
+    pub(crate) fn source_bytes(&self, text: &str, chars: Range<usize>) -> Range<usize> {
+        let (mut start, mut end) = (text.len(), text.len());
+        let kept = text
+            .char_indices()
+            .filter(|&(_, c)| self.fold(c) != Fold::Drop);
+        for (count, (offset, _)) in kept.enumerate() {
+            if count == chars.start {
+                start = offset;
+            }
+            if count == chars.end {
+                end = offset;
+                break;
+            }
+        }
+        start..end
+    }
+
+    /// The normalized form of one character, or `None` if it is dropped.
+    pub fn normalize_char(&self, c: char) -> Option<char> {
+        match self.fold(c) {
+            Fold::Keep => Some(c),
+            Fold::Into(replacement) => Some(replacement),
+            Fold::Drop => None,
+        }
+    }
+
+    fn fold(&self, c: char) -> Fold {
+        (c as u32)
+            .checked_sub(ARABIC_BLOCK)
+            .and_then(|index| self.table.get(index as usize))
+            .copied()
+            .unwrap_or(Fold::Keep)
+    }
+
+    fn with_steps(steps: Steps) -> Self {
+        Self {
+            steps,
+            table: [Fold::Keep; TABLE_SIZE],
+        }
+        .rebuild()
+    }
+
+    fn rebuild(mut self) -> Self {
+        let steps = self.steps;
+        for (index, fold) in self.table.iter_mut().enumerate() {
+            let c = char::from_u32(ARABIC_BLOCK + index as u32).unwrap_or_default();
+            *fold = match c {
+                '\u{064B}'..='\u{065F}' | '\u{0670}' if steps.harakat => Fold::Drop,
+                'ـ' if steps.tatweel => Fold::Drop,
+                'أ' | 'إ' | 'آ' | 'ٱ' if steps.alef_variants => Fold::Into('ا'),
+                'ٱ' if steps.alef_wasla => Fold::Into('ا'),
+                'ؤ' | 'ئ' | 'أ' | 'إ' if steps.hamza_seats => Fold::Into('ء'),
+                'ى' if steps.alef_maqsura => Fold::Into('ي'),
+                'ة' if steps.ta_marbuta => Fold::Into('ه'),
+                '\u{06D6}'..='\u{06DC}' | '\u{06DF}'..='\u{06E8}' | '\u{06EA}'..='\u{06ED}'
+                    if steps.quranic_marks =>
+                {
+                    Fold::Drop
+                }
+                '٠'..='٩' | '۰'..='۹' if steps.digits => Fold::Drop,
+                '،' | '؛' | '؟' if steps.punctuation => Fold::Drop,
+                _ => Fold::Keep,
+            };
+        }
+        self
+    }
+}
+
+/// The shared [`Normalizer::new`] instance.
+pub(crate) fn default_normalizer() -> &'static Normalizer {
+    &DEFAULT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_matches_remove_diacritics() {
+        let normalizer = Normalizer::new();
+        assert_eq!(
+            normalizer.normalize("بِسۡمِ ٱللَّهِ ٱلرَّحۡمَٰنِ ٱلرَّحِيمِ ١"),
+            "بسم الله الرحمن الرحيم "
+        );
+        assert_eq!(normalizer.normalize("أَرۡضٌ، مَعَ"), "أرض مع");
+    }
+
+    #[test]
+    fn test_toggles() {
+        assert_eq!(Normalizer::none().normalize("ٱلۡحَمۡدُ"), "ٱلۡحَمۡدُ");
+        assert_eq!(Normalizer::new().tatweel(true).normalize("الـلـه"), "الله");
+        assert_eq!(Normalizer::new().alef_maqsura(true).normalize("عَلَىٰ"), "علي");
+        assert_eq!(
+            Normalizer::new().hamza_seats(true).normalize("مُؤۡمِنٌ"),
+            "مءمن"
+        );
+        assert_eq!(Normalizer::new().harakat(false).normalize("رَبِّ"), "رَبِّ");
+        assert_eq!(Normalizer::new().digits(false).normalize("٢"), "٢");
+    }
+
+    #[test]
+    fn test_source_bytes() {
+        let normalizer = Normalizer::new();
+        let text = "بِسۡمِ ٱللَّهِ ٱلرَّحۡمَٰنِ";
+        // "الله" is characters 4..8 of "بسم الله الرحمن".
+        assert_eq!(&text[normalizer.source_bytes(text, 4..8)], "ٱللَّهِ");
+        assert_eq!(&text[normalizer.source_bytes(text, 9..15)], "ٱلرَّحۡمَٰنِ");
+        assert_eq!(&text[normalizer.source_bytes(text, 0..2)], "بِسۡ");
+    }
+
+    #[test]
+    fn test_full() {
+        let normalizer = Normalizer::full();
+        assert_eq!(normalizer.normalize("إِلَىٰ"), "الي");
+        assert_eq!(normalizer.normalize("ٱلۡأٓخِرَةِ"), "الاخره");
+        assert_eq!(normalizer.normalize("سُئِلَ"), "سءل");
+    }
+}
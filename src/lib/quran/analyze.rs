@@ -1,8 +1,19 @@
 use std::collections::HashMap;
 
-use crate::traits::TotalLetters;
+use crate::{
+    error::QuranError,
+    letters::{LetterPolicy, LetterStats},
+};
 
-use super::Quran;
+use super::{
+    division::Scope,
+    layer::TextLayer,
+    meta::muqattaat,
+    reference::VerseRange,
+    surah::{RevelationType, Surah},
+    verse::Ayah,
+    Quran,
+};
 
 #[derive(Debug)]
 pub struct Summary {
@@ -10,9 +21,13 @@ pub struct Summary {
     pub total_ayahs: usize,
     pub total_letters: usize,
     pub total_words: usize,
+    pub meccan_surahs: usize,
+    pub medinan_surahs: usize,
     pub longest_surah_name: String,
+    pub longest_surah_transliteration: Option<String>,
     pub longest_surah_letters: usize,
     pub shortest_surah_name: String,
+    pub shortest_surah_transliteration: Option<String>,
     pub shortest_surah_letters: usize,
     pub most_common_word: Option<(String, i32)>,
 }
@@ -24,49 +39,279 @@ impl Default for Summary {
             total_ayahs: Default::default(),
             total_letters: Default::default(),
             total_words: Default::default(),
+            meccan_surahs: Default::default(),
+            medinan_surahs: Default::default(),
             longest_surah_name: Default::default(),
+            longest_surah_transliteration: Default::default(),
             longest_surah_letters: Default::default(),
             shortest_surah_name: Default::default(),
+            shortest_surah_transliteration: Default::default(),
             shortest_surah_letters: usize::MAX,
             most_common_word: Default::default(),
         }
     }
 }
 
+/// [`Summary`] of the Meccan and of the Medinan surahs, side by side.
+#[derive(Debug)]
+pub struct RevelationSummary {
+    pub meccan: Summary,
+    pub medinan: Summary,
+}
+
+/// Counts for one surah.
+#[derive(Debug, Clone)]
+pub struct SurahStats {
+    pub id: u32,
+    pub name: String,
+    pub revelation_type: Option<RevelationType>,
+    pub revelation_order: Option<u32>,
+    pub ayahs: usize,
+    pub words: usize,
+    pub letters: usize,
+}
+
+/// Letter counts of the surahs that open with the same disjoined letters.
+#[derive(Debug, Clone)]
+pub struct MuqattaatGroup {
+    /// The opening letters, e.g. "حم".
+    pub letters: &'static str,
+    pub surahs: Vec<u32>,
+    pub stats: LetterStats,
+}
+
+/// Letter counts of the surahs that open with disjoined letters against the
+/// rest, for comparing their distributions:
+///
+/// ```
+/// # use quran::quran::{analyze::Analyzer, Quran};
+/// let quran = Quran::new().unwrap();
+/// let comparison = Analyzer::new(&quran).compare_muqattaat();
+/// let ha_mim = comparison.group("حم").unwrap();
+/// assert_eq!(ha_mim.surahs, vec![40, 41, 43, 44, 45, 46]);
+/// // How many times more often ح occurs in them than elsewhere.
+/// let lift = ha_mim.stats.relative_to(&comparison.rest, 'ح');
+/// ```
+#[derive(Debug, Clone)]
+pub struct MuqattaatComparison {
+    /// One group for each distinct opening, in text order.
+    pub groups: Vec<MuqattaatGroup>,
+    /// All the surahs that open with disjoined letters.
+    pub opening: LetterStats,
+    /// The surahs that do not.
+    pub rest: LetterStats,
+}
+
+impl MuqattaatComparison {
+    /// The group of surahs opening with exactly `letters`.
+    pub fn group(&self, letters: &str) -> Option<&MuqattaatGroup> {
+        self.groups.iter().find(|group| group.letters == letters)
+    }
+}
+
 pub struct Analyzer<'a> {
     quran: &'a Quran,
+    layer: TextLayer,
+    scope: Option<VerseRange>,
+    letter_policy: LetterPolicy,
 }
 
 impl Analyzer<'_> {
-    pub fn new(quran: &Quran) -> Analyzer {
-        Analyzer { quran }
+    pub fn new(quran: &Quran) -> Analyzer<'_> {
+        Analyzer {
+            quran,
+            layer: quran.primary_layer().clone(),
+            scope: None,
+            letter_policy: LetterPolicy::default(),
+        }
+    }
+
+    /// Counts words and letters in `layer` instead of the primary text.
+    pub fn with_layer(mut self, layer: TextLayer) -> Result<Self, QuranError> {
+        self.quran.check_layer(&layer)?;
+        self.layer = layer;
+        Ok(self)
+    }
+
+    /// Counts letters under `policy`, e.g. [`LetterPolicy::spoken`] to
+    /// include the dagger alef and doubled letters.
+    pub fn with_letter_policy(mut self, policy: LetterPolicy) -> Self {
+        self.letter_policy = policy;
+        self
+    }
+
+    /// Counts only the ayahs of `scope`, such as a juz' or a page. Surahs
+    /// count with their ayahs in the scope, and surahs without any are left
+    /// out.
+    pub fn with_scope(mut self, scope: Scope) -> Result<Self, QuranError> {
+        self.scope = Some(scope.range_in(self.quran)?);
+        Ok(self)
     }
 
     pub fn analyze(&self) -> Summary {
+        self.summarize(|_| true)
+    }
+
+    /// Summarizes the Meccan and the Medinan surahs separately. Surahs whose
+    /// revelation type is unknown are in neither.
+    pub fn analyze_by_revelation_type(&self) -> RevelationSummary {
+        RevelationSummary {
+            meccan: self.summarize(|surah| surah.revelation_type() == Some(RevelationType::Meccan)),
+            medinan: self
+                .summarize(|surah| surah.revelation_type() == Some(RevelationType::Medinan)),
+        }
+    }
+
+    /// Counts for each surah, in the order of revelation; see
+    /// [`Quran::surahs_in_revelation_order`].
+    pub fn surahs_in_revelation_order(&self) -> Vec<SurahStats> {
+        self.quran
+            .surahs_in_revelation_order()
+            .into_iter()
+            .filter_map(|surah| {
+                let ayahs = self.ayahs(surah);
+                if ayahs.is_empty() {
+                    return None;
+                }
+                Some(SurahStats {
+                    id: surah.id(),
+                    name: surah.name(),
+                    revelation_type: surah.revelation_type(),
+                    revelation_order: surah.revelation_order(),
+                    ayahs: ayahs.len(),
+                    words: ayahs
+                        .iter()
+                        .map(|ayah| ayah.words_in(&self.layer).len())
+                        .sum(),
+                    letters: ayahs
+                        .iter()
+                        .map(|ayah| ayah.total_letters_in(&self.layer, &self.letter_policy))
+                        .sum(),
+                })
+            })
+            .collect()
+    }
+
+    /// Counts of each letter under the letter policy. Limit it to a surah or
+    /// a range of ayahs with [`Analyzer::with_scope`].
+    pub fn letter_stats(&self) -> LetterStats {
+        let mut stats = LetterStats::new();
+        for (_, surah_stats) in self.letter_stats_by_surah() {
+            stats.merge(&surah_stats);
+        }
+        stats
+    }
+
+    /// Counts of each letter for every surah with ayahs in scope, by surah
+    /// number.
+    pub fn letter_stats_by_surah(&self) -> Vec<(u32, LetterStats)> {
+        self.quran
+            .surahs()
+            .iter()
+            .filter_map(|surah| {
+                let ayahs = self.ayahs(surah);
+                if ayahs.is_empty() {
+                    return None;
+                }
+                let mut stats = LetterStats::new();
+                for ayah in ayahs {
+                    stats.add(
+                        ayah.text_in(&self.layer).unwrap_or_default(),
+                        &self.letter_policy,
+                    );
+                }
+                Some((surah.id(), stats))
+            })
+            .collect()
+    }
+
+    /// Compares the letters of the surahs that open with disjoined letters,
+    /// such as الم, حم and طس, with those of the other surahs.
+    pub fn compare_muqattaat(&self) -> MuqattaatComparison {
+        let mut comparison = MuqattaatComparison {
+            groups: Vec::new(),
+            opening: LetterStats::new(),
+            rest: LetterStats::new(),
+        };
+        for (id, stats) in self.letter_stats_by_surah() {
+            let Some(letters) = muqattaat(id) else {
+                comparison.rest.merge(&stats);
+                continue;
+            };
+            comparison.opening.merge(&stats);
+            match comparison
+                .groups
+                .iter_mut()
+                .find(|group| group.letters == letters)
+            {
+                Some(group) => {
+                    group.surahs.push(id);
+                    group.stats.merge(&stats);
+                }
+                None => comparison.groups.push(MuqattaatGroup {
+                    letters,
+                    surahs: vec![id],
+                    stats,
+                }),
+            }
+        }
+        comparison
+    }
+
+    /// The ayahs of `surah` in scope.
+    fn ayahs<'s>(&self, surah: &'s Surah) -> Vec<&'s Ayah> {
+        surah
+            .ayahs()
+            .iter()
+            .filter(|ayah| {
+                self.scope
+                    .is_none_or(|scope| scope.contains(&ayah.verse_ref()))
+            })
+            .collect()
+    }
+
+    fn summarize<F>(&self, include: F) -> Summary
+    where
+        F: Fn(&Surah) -> bool,
+    {
         let mut word_counts = HashMap::new();
         let mut summary = Summary::default();
 
-        for surah in self.quran.surahs() {
+        for surah in self.quran.surahs().iter().filter(|surah| include(surah)) {
+            let ayahs = self.ayahs(surah);
+            if ayahs.is_empty() {
+                continue;
+            }
+
             let mut surah_letters = 0;
-            for ayah in surah.ayahs() {
-                for word in ayah.words() {
+            for ayah in ayahs {
+                for word in ayah.words_in(&self.layer) {
                     let lowercase_word = word.to_lowercase();
                     *word_counts.entry(lowercase_word).or_insert(0) += 1;
                     summary.total_words += 1;
                 }
 
                 summary.total_ayahs += 1;
-                surah_letters += ayah.total_letters();
+                surah_letters += ayah.total_letters_in(&self.layer, &self.letter_policy);
             }
 
             if surah_letters > summary.longest_surah_letters {
                 summary.longest_surah_letters = surah_letters;
                 summary.longest_surah_name = surah.name();
+                summary.longest_surah_transliteration = surah.transliteration().map(str::to_string);
             }
 
             if surah_letters < summary.shortest_surah_letters {
                 summary.shortest_surah_letters = surah_letters;
                 summary.shortest_surah_name = surah.name();
+                summary.shortest_surah_transliteration =
+                    surah.transliteration().map(str::to_string);
+            }
+
+            match surah.revelation_type() {
+                Some(RevelationType::Meccan) => summary.meccan_surahs += 1,
+                Some(RevelationType::Medinan) => summary.medinan_surahs += 1,
+                None => {}
             }
 
             summary.total_letters += surah_letters;
@@ -78,3 +323,129 @@ impl Analyzer<'_> {
         summary
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{quran::division::Division, traits::TotalLetters};
+
+    #[test]
+    fn test_analyze_by_revelation_type() {
+        let quran = Quran::new().unwrap();
+        let analyzer = Analyzer::new(&quran);
+
+        let total = analyzer.analyze();
+        let split = analyzer.analyze_by_revelation_type();
+        assert_eq!(split.meccan.total_surahs, split.meccan.meccan_surahs);
+        assert_eq!(split.medinan.total_surahs, split.medinan.medinan_surahs);
+        assert_eq!(split.medinan.meccan_surahs, 0);
+        assert_eq!(
+            split.meccan.total_surahs + split.medinan.total_surahs,
+            total.total_surahs
+        );
+        assert_eq!(
+            split.meccan.total_ayahs + split.medinan.total_ayahs,
+            total.total_ayahs
+        );
+        assert_eq!(
+            split.medinan.longest_surah_transliteration.as_deref(),
+            Some("Al-Baqarah")
+        );
+    }
+
+    #[test]
+    fn test_surahs_in_revelation_order() {
+        let quran = Quran::new().unwrap();
+        let surahs = Analyzer::new(&quran).surahs_in_revelation_order();
+        assert_eq!(surahs.len(), 114);
+        assert_eq!((surahs[0].id, surahs[0].ayahs), (96, 19));
+        assert!(surahs
+            .windows(2)
+            .all(|pair| pair[0].revelation_order < pair[1].revelation_order));
+
+        let juz = Analyzer::new(&quran)
+            .with_scope(Scope::Division(Division::Juz, 30))
+            .unwrap();
+        assert_eq!(juz.surahs_in_revelation_order().len(), 37);
+        assert_eq!(juz.analyze().total_ayahs, 564);
+    }
+
+    #[test]
+    fn test_letter_policy() {
+        let quran = Quran::new().unwrap();
+        let fatihah = quran.surah(0);
+        assert_eq!(fatihah.ayahs()[0].total_letters(), 19);
+        assert_eq!(
+            fatihah.total_letters(),
+            fatihah
+                .ayahs()
+                .iter()
+                .map(|ayah| ayah.total_letters())
+                .sum::<usize>()
+        );
+
+        let scope = Scope::Surah(1);
+        let letters = Analyzer::new(&quran)
+            .with_scope(scope)
+            .unwrap()
+            .analyze()
+            .total_letters;
+        assert_eq!(letters, fatihah.total_letters());
+        let spoken = Analyzer::new(&quran)
+            .with_scope(scope)
+            .unwrap()
+            .with_letter_policy(LetterPolicy::spoken())
+            .analyze()
+            .total_letters;
+        assert_eq!(spoken, fatihah.total_letters_with(&LetterPolicy::spoken()));
+        assert!(spoken > letters);
+    }
+
+    #[test]
+    fn test_letter_stats() {
+        let quran = Quran::new().unwrap();
+        let analyzer = Analyzer::new(&quran);
+
+        let stats = analyzer.letter_stats();
+        assert_eq!(stats.total(), analyzer.analyze().total_letters);
+        let (most_common, _) = stats
+            .alphabet()
+            .into_iter()
+            .max_by_key(|&(_, count)| count)
+            .unwrap();
+        assert_eq!(most_common, 'ا');
+
+        let by_surah = analyzer.letter_stats_by_surah();
+        assert_eq!(by_surah.len(), 114);
+        assert_eq!(by_surah[0].1.total(), quran.surah(0).total_letters());
+
+        let range = Analyzer::new(&quran)
+            .with_scope(Scope::Verses("112:1-2".parse().unwrap()))
+            .unwrap()
+            .letter_stats();
+        assert_eq!(range.count('ل'), 6);
+    }
+
+    #[test]
+    fn test_compare_muqattaat() {
+        let quran = Quran::new().unwrap();
+        let comparison = Analyzer::new(&quran).compare_muqattaat();
+
+        let surahs: usize = comparison
+            .groups
+            .iter()
+            .map(|group| group.surahs.len())
+            .sum();
+        assert_eq!(surahs, 29);
+        assert_eq!(comparison.groups[0].letters, "الم");
+        assert_eq!(comparison.group("الم").unwrap().surahs.len(), 6);
+        assert_eq!(comparison.group("ق").unwrap().surahs, vec![50]);
+        assert_eq!(
+            comparison.opening.total() + comparison.rest.total(),
+            Analyzer::new(&quran).letter_stats().total()
+        );
+        // Surah Qaf has more qaf than the surahs without an opening.
+        let qaf = comparison.group("ق").unwrap();
+        assert!(qaf.stats.relative_to(&comparison.rest, 'ق').unwrap() > 1.0);
+    }
+}
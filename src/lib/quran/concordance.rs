@@ -2,21 +2,82 @@ use std::{collections::HashMap, fs::File};
 
 use prettytable::Table;
 
-use super::Quran;
+use crate::{error::QuranError, normalizer::Normalizer, roots::RootExtractor};
+
+use super::{division::Scope, layer::TextLayer, surah::Surah, verse::Ayah, Quran};
+
+/// One place a word occurs, with the English gloss of its ayah when available.
+#[derive(Debug, Clone)]
+pub struct Occurrence {
+    /// The word as written in the ayah.
+    pub word: String,
+    pub ayah_number: usize,
+    pub surah_name: String,
+    pub text: String,
+    pub translation: Option<String>,
+}
+
+type Occurrences = (usize, Vec<Occurrence>);
+
+/// What the concordance groups occurrences under.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConcordanceKey {
+    /// The word as written.
+    #[default]
+    Surface,
+    /// The word with attached particles and pronouns removed, so that
+    /// "وَٱلۡأَرۡضِ" and "ٱلۡأَرۡضِ" share the entry "أرض".
+    Stem,
+    /// The extracted root, e.g. "ك-ت-ب" for كِتَٰب, كَاتِب and يَكۡتُبُونَ. Words
+    /// without a root (particles, pronouns, the divine name) are left out.
+    Root,
+}
 
 pub struct Concordance<'a> {
     quran: &'a Quran,
-    concordance: HashMap<String, (usize, Vec<(usize, String, String)>)>,
+    layer: TextLayer,
+    key: ConcordanceKey,
+    normalizer: Option<Normalizer>,
+    concordance: HashMap<String, Occurrences>,
 }
 
 impl<'a> Concordance<'a> {
     pub fn new(quran: &'a Quran) -> Self {
         Self {
             quran,
+            layer: quran.primary_layer().clone(),
+            key: ConcordanceKey::default(),
+            normalizer: None,
             concordance: HashMap::new(),
         }
     }
 
+    /// Groups entries by `key` instead of the surface word.
+    pub fn key_by(mut self, key: ConcordanceKey) -> Self {
+        self.key = key;
+        self
+    }
+
+    /// Folds surface and stem keys with `normalizer`, so that spellings which
+    /// differ only in marks or letter variants share an entry. Root keys are
+    /// already normalized. Without one, words are keyed as written.
+    pub fn with_normalizer(mut self, normalizer: Normalizer) -> Self {
+        self.normalizer = Some(normalizer);
+        self
+    }
+
+    /// Builds the concordance from `layer` instead of the primary text.
+    pub fn with_layer(mut self, layer: TextLayer) -> Result<Self, QuranError> {
+        self.quran.check_layer(&layer)?;
+        self.layer = layer;
+        Ok(self)
+    }
+
+    /// Adds the ayahs of surah `surah_number` (counting from 1) after the
+    /// first `start_ayah_number` and up to ayah `end_ayah_number`, or the whole
+    /// text without a surah. Surah 0 is read as 1, and ayah numbers past the
+    /// end of the surah are clamped; a surah that does not exist adds nothing.
+    /// [`Concordance::try_generate`] rejects such input instead.
     pub fn generate<T, U, V>(&mut self, surah_number: T, start_ayah_number: U, end_ayah_number: V)
     where
         T: Into<Option<usize>>,
@@ -25,52 +86,155 @@ impl<'a> Concordance<'a> {
     {
         let start_ayah_number = start_ayah_number.into().unwrap_or(0);
 
-        if let Some(mut surah_number) = surah_number.into() {
-            if surah_number == 0 {
-                surah_number = 1
-            }
-            let surah = self.quran.surah(surah_number - 1);
+        if let Some(surah_number) = surah_number.into() {
+            let Ok(surah) = self.quran.get_surah(surah_number.max(1)) else {
+                return;
+            };
             let ayahs_count = surah.ayahs().len();
 
-            let mut end_ayah_number = end_ayah_number.into().unwrap_or(ayahs_count);
+            let start_ayah_number = start_ayah_number.min(ayahs_count);
+            let end_ayah_number = end_ayah_number
+                .into()
+                .unwrap_or(ayahs_count)
+                .clamp(start_ayah_number, ayahs_count);
 
-            if end_ayah_number > ayahs_count {
-                end_ayah_number = ayahs_count;
+            for ayah in &surah.ayahs()[start_ayah_number..end_ayah_number] {
+                self.add_ayah(surah, ayah);
             }
+        } else {
+            self.add_all();
+        }
+    }
+
+    /// Like [`Concordance::generate`], but fails with
+    /// [`QuranError::SurahOutOfRange`] or [`QuranError::AyahOutOfRange`]
+    /// instead of adjusting numbers that are out of range. An end before the
+    /// start adds nothing.
+    pub fn try_generate<T, U, V>(
+        &mut self,
+        surah_number: T,
+        start_ayah_number: U,
+        end_ayah_number: V,
+    ) -> Result<(), QuranError>
+    where
+        T: Into<Option<usize>>,
+        U: Into<Option<usize>>,
+        V: Into<Option<usize>>,
+    {
+        let Some(surah_number) = surah_number.into() else {
+            self.add_all();
+            return Ok(());
+        };
+        let surah = self.quran.get_surah(surah_number)?;
+        let ayahs_count = surah.ayahs().len();
+
+        let out_of_range = |ayah: usize| QuranError::AyahOutOfRange {
+            surah: surah.id(),
+            ayah: ayah as u32,
+            ayahs: ayahs_count,
+        };
+        let start_ayah_number = start_ayah_number.into().unwrap_or(0);
+        if start_ayah_number > ayahs_count {
+            return Err(out_of_range(start_ayah_number));
+        }
+        let end_ayah_number = end_ayah_number.into().unwrap_or(ayahs_count);
+        if end_ayah_number > ayahs_count {
+            return Err(out_of_range(end_ayah_number));
+        }
+
+        for ayah in surah.ayahs()[start_ayah_number..end_ayah_number.max(start_ayah_number)].iter()
+        {
+            self.add_ayah(surah, ayah);
+        }
+        Ok(())
+    }
 
-            if end_ayah_number < start_ayah_number {
-                end_ayah_number = start_ayah_number;
+    /// Adds the ayahs of `scope`, such as a juz' or a page. Fails when the
+    /// scope names a division that does not exist or ayahs that are not in
+    /// the text.
+    pub fn generate_scope(&mut self, scope: Scope) -> Result<(), QuranError> {
+        let quran = self.quran;
+        for ayah in scope.range_in(quran)?.resolve(quran)? {
+            self.add_ayah(&quran.surahs()[ayah.surah_id() as usize - 1], ayah);
+        }
+        Ok(())
+    }
+
+    fn add_all(&mut self) {
+        for surah in self.quran.surahs() {
+            for ayah in surah.ayahs() {
+                self.add_ayah(surah, ayah);
             }
+        }
+    }
 
-            for ayah in &surah.ayahs()[start_ayah_number..end_ayah_number] {
-                for word in ayah.words() {
-                    self.concordance
-                        .entry(word.to_string())
-                        .or_insert((0, vec![]))
-                        .0 += 1;
-                    self.concordance.get_mut(word).unwrap().1.push((
-                        ayah.number(),
-                        surah.name().to_owned(),
-                        ayah.text().to_string(),
-                    ));
-                }
+    /// Returns the occurrences recorded for `word`, if any. `word` is folded
+    /// with the concordance's normalizer first.
+    pub fn occurrences(&self, word: &str) -> Option<&[Occurrence]> {
+        self.concordance
+            .get(&self.normalize_key(word))
+            .map(|(_, occurrences)| occurrences.as_slice())
+    }
+
+    /// The distinct written forms recorded under `key`, most frequent first.
+    /// Most useful with [`ConcordanceKey::Root`].
+    pub fn forms(&self, key: &str) -> Vec<(&str, usize)> {
+        let mut forms: Vec<(&str, usize)> = Vec::new();
+        for occurrence in self.occurrences(key).unwrap_or_default() {
+            match forms.iter_mut().find(|(form, _)| *form == occurrence.word) {
+                Some((_, count)) => *count += 1,
+                None => forms.push((&occurrence.word, 1)),
             }
-        } else {
-            for surah in self.quran.surahs() {
-                for ayah in surah.ayahs() {
-                    for word in ayah.words() {
-                        self.concordance
-                            .entry(word.to_string())
-                            .or_insert((0, vec![]))
-                            .0 += 1;
-                        self.concordance.get_mut(word).unwrap().1.push((
-                            ayah.number(),
-                            surah.name().to_owned(),
-                            ayah.text().to_string(),
-                        ));
-                    }
-                }
+        }
+        forms.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        forms
+    }
+
+    fn add_ayah(&mut self, surah: &Surah, ayah: &Ayah) {
+        let keyed_words: Vec<(String, &str)> = match self.key {
+            ConcordanceKey::Surface => ayah
+                .words_in(&self.layer)
+                .into_iter()
+                .map(|word| (word.to_string(), word))
+                .collect(),
+            ConcordanceKey::Stem => ayah
+                .tokens_in(&self.layer)
+                .into_iter()
+                .map(|token| (token.stem, token.surface))
+                .collect(),
+            ConcordanceKey::Root => {
+                let extractor = RootExtractor::new();
+                ayah.tokens_in(&self.layer)
+                    .into_iter()
+                    .filter_map(|token| {
+                        let root = extractor.extract(token.surface)?;
+                        Some((root.to_string(), token.surface))
+                    })
+                    .collect()
             }
+        };
+
+        for (key, word) in keyed_words {
+            let key = match self.key {
+                ConcordanceKey::Root => key,
+                _ => self.normalize_key(&key),
+            };
+            let entry = self.concordance.entry(key).or_insert((0, vec![]));
+            entry.0 += 1;
+            entry.1.push(Occurrence {
+                word: word.to_string(),
+                ayah_number: ayah.number(),
+                surah_name: surah.name(),
+                text: ayah.text_in(&self.layer).unwrap_or_default().to_string(),
+                translation: ayah.translation().map(str::to_string),
+            });
+        }
+    }
+
+    fn normalize_key(&self, key: &str) -> String {
+        match &self.normalizer {
+            Some(normalizer) => normalizer.normalize(key),
+            None => key.to_string(),
         }
     }
 
@@ -80,7 +244,7 @@ impl<'a> Concordance<'a> {
 
         let width = 15;
 
-        table.add_row(vec!["الكلمة", "العدد", "رقم الأية", "السورة", "الأية"].into());
+        table.add_row(vec!["الكلمة", "العدد", "رقم الأية", "السورة", "الأية", "الترجمة"].into());
 
         let mut sorted_pairs = self.concordance.iter().collect::<Vec<_>>();
         sorted_pairs.sort_by_key(|(_, (count, _))| *count);
@@ -92,8 +256,17 @@ impl<'a> Concordance<'a> {
 
             table.add_row(vec![word, &count.to_string(), "", ""].into());
 
-            for (ayah_number, sura, ayah_text) in ayahs {
-                table.add_row(vec!["", "", &ayah_number.to_string(), &sura.to_string(), ayah_text].into());
+            if self.key == ConcordanceKey::Root {
+                for (form, form_count) in self.forms(word) {
+                    table.add_row(vec![form, &form_count.to_string(), "", ""].into());
+                    for occurrence in ayahs.iter().filter(|o| o.word == form) {
+                        Self::add_occurrence_row(&mut table, occurrence);
+                    }
+                }
+            } else {
+                for occurrence in ayahs {
+                    Self::add_occurrence_row(&mut table, occurrence);
+                }
             }
         }
 
@@ -104,6 +277,7 @@ impl<'a> Concordance<'a> {
                 "-".repeat(width),
                 "-".repeat(width),
                 "-".repeat(width),
+                "-".repeat(width),
             ]
             .into(),
         );
@@ -119,4 +293,105 @@ impl<'a> Concordance<'a> {
         let mut file = File::create(file_path).expect("Could not create file");
         table.print(&mut file).unwrap();
     }
+
+    fn add_occurrence_row(table: &mut Table, occurrence: &Occurrence) {
+        table.add_row(
+            vec![
+                "",
+                "",
+                &occurrence.ayah_number.to_string(),
+                &occurrence.surah_name,
+                &occurrence.text,
+                occurrence.translation.as_deref().unwrap_or(""),
+            ]
+            .into(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quran::division::Division;
+
+    #[test]
+    fn test_root_concordance() {
+        let quran = Quran::new().unwrap();
+        let mut concordance = Concordance::new(&quran).key_by(ConcordanceKey::Root);
+        concordance.generate(1, None, None);
+
+        let forms = concordance.forms("ر-ح-م");
+        assert_eq!(forms.len(), 2);
+        assert_eq!(concordance.occurrences("ر-ح-م").unwrap().len(), 4);
+        assert!(concordance.occurrences("ٱللَّهِ").is_none());
+    }
+
+    #[test]
+    fn test_stem_concordance() {
+        let quran = Quran::new().unwrap();
+        let mut concordance = Concordance::new(&quran).key_by(ConcordanceKey::Stem);
+        concordance.generate(1, None, None);
+
+        // ٱللَّهِ in 1:1 and لِلَّهِ in 1:2
+        let occurrences = concordance.occurrences("الله").unwrap();
+        assert_eq!(occurrences.len(), 2);
+        assert_eq!(occurrences[1].ayah_number, 2);
+    }
+
+    #[test]
+    fn test_normalized_concordance() {
+        let quran = Quran::new().unwrap();
+        let mut concordance = Concordance::new(&quran).with_normalizer(Normalizer::full());
+        concordance.generate(2, 0, 5);
+
+        // ٱلۡكِتَٰبُ in 2:2 and ٱلۡأٓخِرَةِ in 2:4
+        assert_eq!(concordance.occurrences("الكتب").unwrap().len(), 1);
+        assert_eq!(concordance.occurrences("ٱلۡكِتَٰبُ").unwrap().len(), 1);
+        assert!(concordance.occurrences("وبالآخرة").is_some());
+    }
+
+    #[test]
+    fn test_try_generate() {
+        let quran = Quran::new().unwrap();
+
+        let mut concordance = Concordance::new(&quran).with_normalizer(Normalizer::full());
+        concordance.generate(0, 10, 3);
+        concordance.generate(115, None, None);
+        assert!(concordance.occurrences("الله").is_none());
+
+        let mut concordance = Concordance::new(&quran);
+        assert!(matches!(
+            concordance.try_generate(0, None, None),
+            Err(QuranError::SurahOutOfRange { surah: 0, .. })
+        ));
+        assert!(matches!(
+            concordance.try_generate(1, 8, None),
+            Err(QuranError::AyahOutOfRange { ayah: 8, .. })
+        ));
+        assert!(matches!(
+            concordance.try_generate(1, None, 8),
+            Err(QuranError::AyahOutOfRange { ayah: 8, .. })
+        ));
+        let mut concordance = Concordance::new(&quran).with_normalizer(Normalizer::full());
+        concordance.try_generate(1, 1, 2).unwrap();
+        assert_eq!(concordance.occurrences("لله").unwrap().len(), 1);
+        assert!(concordance.occurrences("الله").is_none());
+    }
+
+    #[test]
+    fn test_generate_scope() {
+        let quran = Quran::new().unwrap();
+
+        let mut concordance = Concordance::new(&quran).with_normalizer(Normalizer::full());
+        concordance
+            .generate_scope(Scope::Division(Division::Page, 604))
+            .unwrap();
+        assert_eq!(concordance.occurrences("قل").unwrap().len(), 3);
+        assert_eq!(concordance.occurrences("الله").unwrap().len(), 2);
+
+        assert!(matches!(
+            concordance.generate_scope(Scope::Division(Division::Juz, 31)),
+            Err(QuranError::DivisionOutOfRange { .. })
+        ));
+    }
 }
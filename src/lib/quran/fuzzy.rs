@@ -0,0 +1,260 @@
+//! Approximate matching of words written in modern orthography against the
+//! Uthmani text.
+//!
+//! Both sides are reduced to a consonantal skeleton (marks dropped, letter
+//! variants folded, the dagger alef spelled out) and compared with a weighted
+//! edit distance. Differences the two orthographies are known for, such as
+//! the و of صلوة against the ا of صلاة or a dropped long vowel, cost less than
+//! a change of consonant.
+
+use std::{collections::HashMap, sync::LazyLock};
+
+use crate::normalizer::Normalizer;
+
+use super::verse::Ayah;
+
+const DAGGER_ALEF: char = '\u{0670}';
+
+/// Cost of inserting or deleting a long vowel or a hamza, and of exchanging
+/// two of them, or ta marbuta with ha or ta.
+const ORTHOGRAPHIC_COST: f32 = 0.5;
+const EDIT_COST: f32 = 1.0;
+
+static SKELETON: LazyLock<Normalizer> = LazyLock::new(Normalizer::full);
+
+/// The best approximate match in one ayah.
+#[derive(Debug, Clone)]
+pub struct FuzzyMatch<'a> {
+    pub ayah: &'a Ayah,
+    /// The matched words as written.
+    pub matched: String,
+    /// Zero-based position of the first matched word.
+    pub word: usize,
+    /// Weighted edit distance between the skeletons; 0 for an exact match.
+    pub distance: f32,
+}
+
+/// The consonantal skeleton of `word`: ٱلۡكِتَٰبُ → الكتاب, ٱلصَّلَوٰةَ → الصلواه.
+pub fn skeleton(word: &str) -> String {
+    let mut spelled = String::with_capacity(word.len());
+    let mut previous = None;
+    for c in word.chars() {
+        if c == DAGGER_ALEF {
+            // Over alef maqsura it only colours the vowel (عَلَىٰ).
+            if previous != Some('ى') {
+                spelled.push('ا');
+            }
+        } else {
+            spelled.push(c);
+            if SKELETON.normalize_char(c).is_some() {
+                previous = Some(c);
+            }
+        }
+    }
+    SKELETON.normalize(&spelled)
+}
+
+/// Weighted edit distance between two skeletons.
+pub fn distance(a: &str, b: &str) -> f32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut previous: Vec<f32> = Vec::with_capacity(b.len() + 1);
+    previous.push(0.0);
+    for &c in &b {
+        previous.push(previous[previous.len() - 1] + indel_cost(c));
+    }
+    let mut current = vec![0.0; b.len() + 1];
+
+    for &ca in &a {
+        current[0] = previous[0] + indel_cost(ca);
+        for (j, &cb) in b.iter().enumerate() {
+            current[j + 1] = (previous[j] + substitution_cost(ca, cb))
+                .min(previous[j + 1] + indel_cost(ca))
+                .min(current[j] + indel_cost(cb));
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+fn is_weak(c: char) -> bool {
+    matches!(c, 'ا' | 'و' | 'ي' | 'ء')
+}
+
+fn indel_cost(c: char) -> f32 {
+    if is_weak(c) {
+        ORTHOGRAPHIC_COST
+    } else {
+        EDIT_COST
+    }
+}
+
+fn substitution_cost(a: char, b: char) -> f32 {
+    if a == b {
+        0.0
+    } else if (is_weak(a) && is_weak(b)) || (matches!(a, 'ه' | 'ت') && matches!(b, 'ه' | 'ت')) {
+        ORTHOGRAPHIC_COST
+    } else {
+        EDIT_COST
+    }
+}
+
+/// The distinct word skeletons of a text layer and where they occur, so that
+/// a fuzzy search measures each skeleton once instead of every word of the
+/// text. Built on first use by [`InvertedIndex`](super::index::InvertedIndex).
+#[derive(Debug, Default)]
+pub(in crate::quran) struct Vocabulary {
+    skeletons: Vec<String>,
+    /// The skeleton of every whitespace-separated word of each ayah, in text
+    /// order.
+    ayahs: Vec<Vec<u32>>,
+    /// The ayahs each skeleton occurs in, in text order.
+    postings: Vec<Vec<u32>>,
+}
+
+impl Vocabulary {
+    /// The vocabulary of the ayah texts in `texts`, in text order.
+    pub(in crate::quran) fn build<'a, I>(texts: I) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut vocabulary = Self::default();
+        let mut ids: HashMap<String, u32> = HashMap::new();
+
+        for (position, text) in texts.into_iter().enumerate() {
+            let words = text
+                .split_whitespace()
+                .map(|word| {
+                    let skeleton = skeleton(word);
+                    let id = *ids.entry(skeleton).or_insert_with_key(|skeleton| {
+                        vocabulary.skeletons.push(skeleton.clone());
+                        vocabulary.postings.push(Vec::new());
+                        vocabulary.skeletons.len() as u32 - 1
+                    });
+                    let postings = &mut vocabulary.postings[id as usize];
+                    if postings.last() != Some(&(position as u32)) {
+                        postings.push(position as u32);
+                    }
+                    id
+                })
+                .collect();
+            vocabulary.ayahs.push(words);
+        }
+
+        vocabulary
+    }
+}
+
+/// Matches `search_term` against consecutive words of each ayah of
+/// `vocabulary`, keeping the closest match per ayah within `max_distance`,
+/// closest first. `ayah` gives the ayah at a position in text order and its
+/// text, or `None` to leave it out.
+pub(in crate::quran) fn search<'a, F>(
+    vocabulary: &Vocabulary,
+    ayah: F,
+    search_term: &str,
+    max_distance: f32,
+) -> Vec<FuzzyMatch<'a>>
+where
+    F: Fn(u32) -> Option<(&'a Ayah, &'a str)>,
+{
+    let terms: Vec<String> = search_term.split_whitespace().map(skeleton).collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    // The distance of every skeleton to each term.
+    let distances: Vec<Vec<f32>> = terms
+        .iter()
+        .map(|term| {
+            vocabulary
+                .skeletons
+                .iter()
+                .map(|skeleton| distance(skeleton, term))
+                .collect()
+        })
+        .collect();
+
+    // Only ayahs with a word close enough to the first term can match.
+    let mut candidates: Vec<u32> = distances[0]
+        .iter()
+        .enumerate()
+        .filter(|(_, &distance)| distance <= max_distance)
+        .flat_map(|(id, _)| vocabulary.postings[id].iter().copied())
+        .collect();
+    candidates.sort_unstable();
+    candidates.dedup();
+
+    let mut matches = Vec::new();
+    for position in candidates {
+        let Some((ayah, text)) = ayah(position) else {
+            continue;
+        };
+        let words = &vocabulary.ayahs[position as usize];
+        let mut best: Option<(usize, f32)> = None;
+
+        for (start, window) in words.windows(terms.len()).enumerate() {
+            let mut total = 0.0;
+            for (i, &id) in window.iter().enumerate() {
+                total += distances[i][id as usize];
+                if total > max_distance {
+                    break;
+                }
+            }
+            if total <= max_distance && best.is_none_or(|(_, d)| total < d) {
+                best = Some((start, total));
+            }
+        }
+
+        if let Some((start, distance)) = best {
+            let matched: Vec<&str> = text
+                .split_whitespace()
+                .skip(start)
+                .take(terms.len())
+                .collect();
+            matches.push(FuzzyMatch {
+                ayah,
+                matched: matched.join(" "),
+                word: start,
+                distance,
+            });
+        }
+    }
+
+    // A stable sort keeps text order among equal distances.
+    matches.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skeleton() {
+        assert_eq!(skeleton("ٱلۡكِتَٰبُ"), "الكتاب");
+        assert_eq!(skeleton("ٱلصَّلَوٰةَ"), "الصلواه");
+        assert_eq!(skeleton("عَلَىٰ"), "علي");
+    }
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance("الكتاب", "الكتاب"), 0.0);
+        assert_eq!(distance("الكتب", "الكتاب"), ORTHOGRAPHIC_COST);
+        assert_eq!(
+            distance(&skeleton("الصلاة"), &skeleton("ٱلصَّلَوٰةَ")),
+            ORTHOGRAPHIC_COST
+        );
+        assert_eq!(distance("كتب", "كسب"), EDIT_COST);
+    }
+
+    #[test]
+    fn test_vocabulary() {
+        let vocabulary = Vocabulary::build(["ٱلۡكِتَٰبُ كِتَٰبٌ", "ٱلۡكِتَٰبِ"]);
+        assert_eq!(vocabulary.skeletons, vec!["الكتاب", "كتاب"]);
+        assert_eq!(vocabulary.ayahs, vec![vec![0, 1], vec![0]]);
+        assert_eq!(vocabulary.postings, vec![vec![0, 1], vec![0]]);
+    }
+}
@@ -0,0 +1,178 @@
+//! Search results with the positions of the matched words, and rendering
+//! them highlighted.
+
+use std::ops::Range;
+
+use super::verse::Ayah;
+
+/// Where a matched word lies in [`SearchHit::text`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub bytes: Range<usize>,
+    pub chars: Range<usize>,
+}
+
+/// One ayah found by a [`QuranSearch`](super::search::QuranSearch).
+#[derive(Debug, Clone)]
+pub struct SearchHit<'a> {
+    pub ayah: &'a Ayah,
+    pub surah_id: u32,
+    pub surah_name: &'a str,
+    pub ayah_number: usize,
+    /// Position of the ayah in the whole text, counting from zero.
+    pub index: usize,
+    /// The ayah in the searched layer, diacritics included. When searching a
+    /// translation this is the translation, and `ayah` has the Arabic.
+    pub text: &'a str,
+    /// Zero-based positions of the matched words among the
+    /// whitespace-separated words of `text`, in order.
+    pub words: Vec<usize>,
+    /// Spans of the matched words in `text`, one per entry of `words`.
+    pub spans: Vec<Span>,
+}
+
+impl<'a> SearchHit<'a> {
+    pub(in crate::quran) fn new<I>(ayah: &'a Ayah, index: usize, text: &'a str, words: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let mut words: Vec<usize> = words.into_iter().collect();
+        words.sort_unstable();
+        words.dedup();
+
+        let mut spans = Vec::with_capacity(words.len());
+        let mut matched = words.iter().peekable();
+        let mut chars = 0;
+        let mut previous_end = 0;
+        for (position, word) in text.split_whitespace().enumerate() {
+            if matched.peek().is_none() {
+                break;
+            }
+            let start = word.as_ptr() as usize - text.as_ptr() as usize;
+            chars += text[previous_end..start].chars().count();
+            let length = word.chars().count();
+            if matched.next_if_eq(&&position).is_some() {
+                spans.push(Span {
+                    bytes: start..start + word.len(),
+                    chars: chars..chars + length,
+                });
+            }
+            chars += length;
+            previous_end = start + word.len();
+        }
+
+        Self {
+            ayah,
+            surah_id: ayah.surah_id(),
+            surah_name: ayah.surah_name(),
+            ayah_number: ayah.number(),
+            index,
+            text,
+            words,
+            spans,
+        }
+    }
+
+    /// The matched words as written.
+    pub fn matched(&self) -> Vec<&'a str> {
+        self.spans
+            .iter()
+            .map(|span| &self.text[span.bytes.clone()])
+            .collect()
+    }
+
+    /// The text with its matched words marked up by `highlighter`.
+    pub fn highlight(&self, highlighter: Highlighter) -> String {
+        highlighter.render(self)
+    }
+}
+
+/// Marks up the matched words of a [`SearchHit`]. Consecutive matched words,
+/// such as a phrase, are marked as one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlighter {
+    /// Bold yellow in a terminal.
+    Ansi,
+    /// `<mark>` elements; the rest of the text is escaped.
+    Html,
+    /// `**bold**`.
+    Markdown,
+}
+
+impl Highlighter {
+    pub fn render(&self, hit: &SearchHit) -> String {
+        let (open, close) = match self {
+            Highlighter::Ansi => ("\x1b[1;33m", "\x1b[0m"),
+            Highlighter::Html => ("<mark>", "</mark>"),
+            Highlighter::Markdown => ("**", "**"),
+        };
+
+        let mut rendered = String::with_capacity(hit.text.len());
+        let mut last = 0;
+        for run in runs(hit) {
+            self.push_text(&mut rendered, &hit.text[last..run.start]);
+            rendered.push_str(open);
+            self.push_text(&mut rendered, &hit.text[run.clone()]);
+            rendered.push_str(close);
+            last = run.end;
+        }
+        self.push_text(&mut rendered, &hit.text[last..]);
+        rendered
+    }
+
+    fn push_text(&self, rendered: &mut String, text: &str) {
+        if *self != Highlighter::Html {
+            rendered.push_str(text);
+            return;
+        }
+        for c in text.chars() {
+            match c {
+                '&' => rendered.push_str("&amp;"),
+                '<' => rendered.push_str("&lt;"),
+                '>' => rendered.push_str("&gt;"),
+                '"' => rendered.push_str("&quot;"),
+                c => rendered.push(c),
+            }
+        }
+    }
+}
+
+/// Byte ranges of the matched spans, merging those separated by whitespace
+/// only.
+fn runs(hit: &SearchHit) -> Vec<Range<usize>> {
+    let mut runs: Vec<Range<usize>> = Vec::new();
+    for span in &hit.spans {
+        match runs.last_mut() {
+            Some(run) if hit.text[run.end..span.bytes.start].trim().is_empty() => {
+                run.end = span.bytes.end;
+            }
+            _ => runs.push(span.bytes.clone()),
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight() {
+        let text = "a <b> c & d";
+        let ayah = Ayah::new(1, 1, "test".to_string(), text.to_string(), None);
+        let hit = SearchHit::new(&ayah, 0, text, [4, 0, 1]);
+
+        assert_eq!(hit.words, vec![0, 1, 4]);
+        assert_eq!(hit.spans[1].bytes, 2..5);
+        assert_eq!(hit.matched(), vec!["a", "<b>", "d"]);
+        assert_eq!(
+            hit.highlight(Highlighter::Html),
+            "<mark>a &lt;b&gt;</mark> c &amp; <mark>d</mark>"
+        );
+        assert_eq!(hit.highlight(Highlighter::Markdown), "**a <b>** c & **d**");
+        assert_eq!(
+            hit.highlight(Highlighter::Ansi),
+            "\x1b[1;33ma <b>\x1b[0m c & \x1b[1;33md\x1b[0m"
+        );
+    }
+}
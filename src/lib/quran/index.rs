@@ -0,0 +1,555 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    sync::OnceLock,
+};
+
+use crate::{analyzer::TextAnalyzer, normalizer::Normalizer, tokenizer::Tokenizer};
+
+use super::{fuzzy::Vocabulary, layer::TextLayer, matching::MatchMode, verse::Ayah, Quran};
+
+/// One occurrence of an indexed word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Posting {
+    /// Position of the ayah in the whole text, counting from zero.
+    pub ayah: u32,
+    /// Zero-based word position within the ayah.
+    pub position: u32,
+}
+
+/// Normalized words and stems of one text layer, mapped to where they occur.
+///
+/// Translation layers are indexed by the terms of their [`TextAnalyzer`] instead,
+/// under every match mode, and the normalizer does not apply to them.
+///
+/// Built once per layer and normalizer by [`Quran::index`] and shared by every
+/// [`QuranSearch`](super::search::QuranSearch) on that text. On Arabic layers,
+/// matching gives the same answers as
+/// [`Ayah::matches_in`](super::verse::Ayah::matches_in); on translation layers
+/// it does not, since `matches_in` knows nothing of stop words or stemming.
+/// The normalized text and the fuzzy vocabulary are built the first time a
+/// search needs them.
+#[derive(Debug, Default)]
+pub struct InvertedIndex {
+    words: HashMap<String, Vec<Posting>>,
+    stems: HashMap<String, Vec<Posting>>,
+    /// Surah and ayah index of each ayah, in text order.
+    locations: Vec<(usize, usize)>,
+    /// Position of each ayah's first word, and of its first token, within
+    /// its surah.
+    word_starts: Vec<u32>,
+    stem_starts: Vec<u32>,
+    /// Number of words, and of tokens, in each ayah.
+    word_counts: Vec<u32>,
+    stem_counts: Vec<u32>,
+    analyzer: Option<TextAnalyzer>,
+    layer: TextLayer,
+    normalizer: Normalizer,
+    /// The normalized text of each ayah, for pattern search.
+    texts: OnceLock<Vec<String>>,
+    /// The word skeletons of the layer, for fuzzy search.
+    vocabulary: OnceLock<Vocabulary>,
+}
+
+/// Matching ayahs, in text order, each with the positions of its matched
+/// words.
+pub type Matches = BTreeMap<u32, BTreeSet<u32>>;
+
+/// How close two words must be for [`InvertedIndex::near`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Proximity {
+    /// At most this many words apart, in either order, counting across
+    /// ayahs of the same surah. Adjacent words are 1 apart.
+    Words(u32),
+    /// In the same ayah or at most this many ayahs apart, in either order.
+    Ayahs(u32),
+    /// The second in one of the next this many ayahs after the first.
+    FollowingAyahs(u32),
+}
+
+impl InvertedIndex {
+    pub fn build(quran: &Quran, layer: &TextLayer, normalizer: &Normalizer) -> Self {
+        let tokenizer = Tokenizer::new();
+        let mut index = Self {
+            analyzer: layer.translation_language().map(TextAnalyzer::for_language),
+            layer: layer.clone(),
+            normalizer: normalizer.clone(),
+            ..Self::default()
+        };
+
+        for (surah_index, surah) in quran.surahs().iter().enumerate() {
+            let mut word_start = 0;
+            let mut stem_start = 0;
+
+            for (ayah_index, ayah) in surah.ayahs().iter().enumerate() {
+                let global = index.locations.len() as u32;
+                index.locations.push((surah_index, ayah_index));
+                index.word_starts.push(word_start);
+                index.stem_starts.push(stem_start);
+
+                let Some(text) = ayah.text_in(layer) else {
+                    index.word_counts.push(0);
+                    index.stem_counts.push(0);
+                    continue;
+                };
+                let (words_before, stems_before) = (word_start, stem_start);
+
+                if let Some(analyzer) = &index.analyzer {
+                    // Stop words keep their positions, so phrases still line up.
+                    for (position, term) in analyzer.analyze(text).into_iter().enumerate() {
+                        word_start += 1;
+                        if let Some(term) = term {
+                            index.words.entry(term).or_default().push(Posting {
+                                ayah: global,
+                                position: position as u32,
+                            });
+                        }
+                    }
+                    stem_start = word_start;
+                    index.word_counts.push(word_start - words_before);
+                    index.stem_counts.push(word_start - words_before);
+                    continue;
+                }
+
+                let normalized = normalizer.normalize(text);
+                for (position, word) in normalized.split_whitespace().enumerate() {
+                    word_start += 1;
+                    index
+                        .words
+                        .entry(word.to_string())
+                        .or_default()
+                        .push(Posting {
+                            ayah: global,
+                            position: position as u32,
+                        });
+                }
+
+                for (position, token) in tokenizer.tokenize(text).into_iter().enumerate() {
+                    stem_start += 1;
+                    let stem = normalizer.normalize(&token.stem);
+                    index.stems.entry(stem).or_default().push(Posting {
+                        ayah: global,
+                        position: position as u32,
+                    });
+                }
+
+                index.word_counts.push(word_start - words_before);
+                index.stem_counts.push(stem_start - stems_before);
+            }
+        }
+
+        index
+    }
+
+    /// For each word position the index gives an ayah with `text` under
+    /// `mode`, the position of that word among the whitespace-separated words
+    /// of `text`.
+    pub(in crate::quran) fn indexed_words(
+        &self,
+        text: &str,
+        mode: MatchMode,
+        normalizer: &Normalizer,
+    ) -> Vec<usize> {
+        match self.analyzer {
+            Some(_) => (0..text.split_whitespace().count()).collect(),
+            None => Self::lettered_words(text, mode, normalizer),
+        }
+    }
+
+    /// [`InvertedIndex::indexed_words`] for Arabic text, where words made
+    /// only of marks are not indexed.
+    pub(in crate::quran) fn lettered_words(
+        text: &str,
+        mode: MatchMode,
+        normalizer: &Normalizer,
+    ) -> Vec<usize> {
+        let tokenizer = Tokenizer::new();
+        text.split_whitespace()
+            .enumerate()
+            .filter(|(_, word)| match mode {
+                MatchMode::Stem => tokenizer.stem(word).is_some(),
+                _ => !normalizer.normalize(word).is_empty(),
+            })
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    /// The ayah at `ayah` in text order. `quran` must be the text the index
+    /// was built from.
+    pub(in crate::quran) fn ayah<'q>(&self, quran: &'q Quran, ayah: u32) -> &'q Ayah {
+        let (surah, ayah) = self.location(ayah);
+        &quran.surahs()[surah].ayahs()[ayah]
+    }
+
+    /// The text of each ayah in the indexed layer after normalization, in
+    /// text order, computed on the first call.
+    pub(in crate::quran) fn normalized_texts(&self, quran: &Quran) -> &[String] {
+        self.texts.get_or_init(|| {
+            (0..self.ayah_count() as u32)
+                .map(|ayah| {
+                    let text = self.ayah(quran, ayah).text_in(&self.layer);
+                    self.normalizer.normalize(text.unwrap_or_default())
+                })
+                .collect()
+        })
+    }
+
+    /// The word skeletons of the indexed layer, computed on the first call.
+    pub(in crate::quran) fn vocabulary(&self, quran: &Quran) -> &Vocabulary {
+        self.vocabulary.get_or_init(|| {
+            Vocabulary::build((0..self.ayah_count() as u32).map(|ayah| {
+                self.ayah(quran, ayah)
+                    .text_in(&self.layer)
+                    .unwrap_or_default()
+            }))
+        })
+    }
+
+    /// Occurrences of an already normalized word.
+    pub fn postings(&self, word: &str) -> &[Posting] {
+        self.words.get(word).map_or(&[], Vec::as_slice)
+    }
+
+    /// Number of distinct normalized words.
+    pub fn vocabulary_len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Number of ayahs in the indexed text.
+    pub fn ayah_count(&self) -> usize {
+        self.locations.len()
+    }
+
+    /// Number of words of the ayah at `ayah` in text order, counted as
+    /// tokens under [`MatchMode::Stem`].
+    pub fn ayah_len(&self, ayah: u32, mode: MatchMode) -> u32 {
+        match mode {
+            MatchMode::Stem => self.stem_counts[ayah as usize],
+            _ => self.word_counts[ayah as usize],
+        }
+    }
+
+    /// Surah and ayah index (both zero-based) of the ayah at `ayah` in text
+    /// order.
+    pub fn location(&self, ayah: u32) -> (usize, usize) {
+        self.locations[ayah as usize]
+    }
+
+    /// The ayahs matching `search_term` under `mode`, in text order.
+    pub fn search(&self, search_term: &str, mode: MatchMode, normalizer: &Normalizer) -> Vec<u32> {
+        self.matches(search_term, mode, normalizer)
+            .into_keys()
+            .collect()
+    }
+
+    /// Like [`InvertedIndex::search`], with the positions of the matched words
+    /// in each ayah (token positions under [`MatchMode::Stem`]).
+    pub fn matches(&self, search_term: &str, mode: MatchMode, normalizer: &Normalizer) -> Matches {
+        let (starts, len) = self.phrase_postings(search_term, mode, normalizer);
+        let mut matches = Matches::new();
+        for start in starts {
+            matches
+                .entry(start.ayah)
+                .or_default()
+                .extend(start.position..start.position + len as u32);
+        }
+        matches
+    }
+
+    /// The ayahs where `left` and `right` occur within `proximity` of each
+    /// other, in text order. Both ayahs of a match that spans an ayah boundary
+    /// are included; matches never span surahs.
+    pub fn near(
+        &self,
+        left: &str,
+        right: &str,
+        proximity: Proximity,
+        mode: MatchMode,
+        normalizer: &Normalizer,
+    ) -> Vec<u32> {
+        self.near_matches(left, right, proximity, mode, normalizer)
+            .into_keys()
+            .collect()
+    }
+
+    /// Like [`InvertedIndex::near`], with the positions of the matched words.
+    pub fn near_matches(
+        &self,
+        left: &str,
+        right: &str,
+        proximity: Proximity,
+        mode: MatchMode,
+        normalizer: &Normalizer,
+    ) -> Matches {
+        let (left, left_len) = self.phrase_postings(left, mode, normalizer);
+        let (right, right_len) = self.phrase_postings(right, mode, normalizer);
+        if left_len == 0 || right_len == 0 {
+            return Matches::new();
+        }
+        let (left_len, right_len) = (left_len as u32, right_len as u32);
+        let starts = match mode {
+            MatchMode::Stem => &self.stem_starts,
+            _ => &self.word_starts,
+        };
+
+        // Right-hand occurrences ordered by surah, then by position in it.
+        let key = |posting: &Posting| match proximity {
+            Proximity::Words(_) => (
+                self.locations[posting.ayah as usize].0,
+                starts[posting.ayah as usize] + posting.position,
+            ),
+            Proximity::Ayahs(_) | Proximity::FollowingAyahs(_) => {
+                (self.locations[posting.ayah as usize].0, posting.ayah)
+            }
+        };
+        let mut right: Vec<((usize, u32), Posting)> = right
+            .into_iter()
+            .map(|posting| (key(&posting), posting))
+            .collect();
+        right.sort_unstable();
+
+        let mut matches = Matches::new();
+        for a in &left {
+            let (surah, at) = key(a);
+            // Distances come from user queries, so every bound saturates.
+            let (low, high) = match proximity {
+                Proximity::Words(n) => (
+                    at.saturating_sub(n.saturating_add(right_len - 1)),
+                    at.saturating_add(left_len - 1).saturating_add(n),
+                ),
+                Proximity::Ayahs(n) => (at.saturating_sub(n), at.saturating_add(n)),
+                Proximity::FollowingAyahs(n) => (at.saturating_add(1), at.saturating_add(n)),
+            };
+            let from = right.partition_point(|(k, _)| *k < (surah, low));
+            let to = right.partition_point(|(k, _)| *k <= (surah, high));
+
+            for ((_, bt), b) in &right[from..to] {
+                let within = match proximity {
+                    // The phrases must not overlap.
+                    Proximity::Words(_) => *bt >= at + left_len || at >= *bt + right_len,
+                    Proximity::Ayahs(_) | Proximity::FollowingAyahs(_) => true,
+                };
+                if within {
+                    matches
+                        .entry(a.ayah)
+                        .or_default()
+                        .extend(a.position..a.position + left_len);
+                    matches
+                        .entry(b.ayah)
+                        .or_default()
+                        .extend(b.position..b.position + right_len);
+                }
+            }
+        }
+
+        matches
+    }
+
+    /// Where each match of `search_term` starts, and how many words it spans.
+    fn phrase_postings(
+        &self,
+        search_term: &str,
+        mode: MatchMode,
+        normalizer: &Normalizer,
+    ) -> (Vec<Posting>, usize) {
+        let terms = self.phrase_terms(search_term, mode, normalizer);
+        let Some(&(span, _)) = terms.last() else {
+            return (Vec::new(), 0);
+        };
+
+        let last = terms.len() - 1;
+        let term_postings: Vec<Vec<Posting>> = terms
+            .iter()
+            .enumerate()
+            .map(|(i, (_, term))| {
+                // A substring spanning several words ends the first word and
+                // starts the last one.
+                let mode = match mode {
+                    MatchMode::Substring if last == 0 => MatchMode::Substring,
+                    MatchMode::Substring if i == 0 => MatchMode::Suffix,
+                    MatchMode::Substring if i == last => MatchMode::Prefix,
+                    MatchMode::Substring => MatchMode::Exact,
+                    mode => mode,
+                };
+                self.matching_postings(term, mode)
+            })
+            .collect();
+
+        let following: Vec<(u32, HashSet<Posting>)> = terms[1..]
+            .iter()
+            .zip(&term_postings[1..])
+            .map(|((offset, _), postings)| (*offset, postings.iter().copied().collect()))
+            .collect();
+
+        let starts = term_postings[0]
+            .iter()
+            .filter(|start| {
+                following.iter().all(|(offset, postings)| {
+                    postings.contains(&Posting {
+                        ayah: start.ayah,
+                        position: start.position + offset,
+                    })
+                })
+            })
+            .copied()
+            .collect();
+        (starts, span as usize + 1)
+    }
+
+    /// The words of `search_term` as they are keyed in the index: normalized,
+    /// their stems under [`MatchMode::Stem`], or analyzed terms in a
+    /// translation.
+    pub(in crate::quran) fn terms(
+        &self,
+        search_term: &str,
+        mode: MatchMode,
+        normalizer: &Normalizer,
+    ) -> Vec<String> {
+        self.phrase_terms(search_term, mode, normalizer)
+            .into_iter()
+            .map(|(_, term)| term)
+            .collect()
+    }
+
+    /// [`InvertedIndex::terms`], each with its word offset from the first.
+    /// Offsets skip the stop words of a translation.
+    fn phrase_terms(
+        &self,
+        search_term: &str,
+        mode: MatchMode,
+        normalizer: &Normalizer,
+    ) -> Vec<(u32, String)> {
+        let terms: Vec<(u32, String)> = match (&self.analyzer, mode) {
+            (Some(analyzer), _) => analyzer
+                .analyze(search_term)
+                .into_iter()
+                .enumerate()
+                .filter_map(|(offset, term)| Some((offset as u32, term?)))
+                .collect(),
+            (None, MatchMode::Stem) => Tokenizer::new()
+                .tokenize(search_term)
+                .into_iter()
+                .enumerate()
+                .map(|(offset, token)| (offset as u32, normalizer.normalize(&token.stem)))
+                .collect(),
+            (None, _) => normalizer
+                .normalize(search_term)
+                .split_whitespace()
+                .enumerate()
+                .map(|(offset, word)| (offset as u32, word.to_string()))
+                .collect(),
+        };
+
+        let first = terms.first().map_or(0, |(offset, _)| *offset);
+        terms
+            .into_iter()
+            .map(|(offset, term)| (offset - first, term))
+            .collect()
+    }
+
+    /// Occurrences of every indexed word that `term`, as returned by
+    /// [`InvertedIndex::terms`], matches under `mode`.
+    pub(in crate::quran) fn matching_postings(&self, term: &str, mode: MatchMode) -> Vec<Posting> {
+        let map = match mode {
+            MatchMode::Stem if self.analyzer.is_none() => &self.stems,
+            _ => &self.words,
+        };
+
+        match mode {
+            MatchMode::Exact | MatchMode::Stem => map.get(term).cloned().unwrap_or_default(),
+            _ => map
+                .iter()
+                .filter(|(word, _)| mode.word_matches(word, term))
+                .flat_map(|(_, postings)| postings.iter().copied())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_agrees_with_scan() {
+        let quran = Quran::new().unwrap();
+        let normalizer = Normalizer::new();
+        let layer = quran.primary_layer().clone();
+        let index = quran.index(&layer, &normalizer);
+        let ayahs = quran.ayas();
+
+        for (term, mode) in [
+            ("الله", MatchMode::Exact),
+            ("بسم الله الرحمن الرحيم", MatchMode::Exact),
+            ("يعلم", MatchMode::Prefix),
+            ("لمين", MatchMode::Suffix),
+            ("ن الرح", MatchMode::Substring),
+            ("علم", MatchMode::Substring),
+            ("الله", MatchMode::Stem),
+        ] {
+            let scanned: Vec<u32> = ayahs
+                .iter()
+                .enumerate()
+                .filter(|(_, ayah)| ayah.matches_in(term, &layer, mode, &normalizer))
+                .map(|(i, _)| i as u32)
+                .collect();
+            assert_eq!(
+                index.search(term, mode, &normalizer),
+                scanned,
+                "{} {:?}",
+                term,
+                mode
+            );
+        }
+    }
+
+    #[test]
+    fn test_near() {
+        let quran = Quran::new().unwrap();
+        let normalizer = Normalizer::new();
+        let index = quran.index(quran.primary_layer(), &normalizer);
+        let near = |left, right, proximity| {
+            index.near(left, right, proximity, MatchMode::Exact, &normalizer)
+        };
+
+        // 1:1 بسم الله الرحمن الرحيم
+        assert_eq!(near("بسم", "الرحيم", Proximity::Words(3))[0], 0);
+        assert!(!near("بسم", "الرحيم", Proximity::Words(2)).contains(&0));
+        assert!(near("الرحيم", "بسم", Proximity::Words(3)).contains(&0));
+
+        // The last word of 1:1 and the first of 1:2, but not 1:7 and 2:1.
+        assert_eq!(near("الرحيم", "الحمد", Proximity::Words(1))[..2], [0, 1]);
+        assert!(near("الضالين", "الم", Proximity::Words(5)).is_empty());
+
+        // رب in 1:2 and the الرحمن of 1:3 after it; the الرحمن of 1:3 has no
+        // رب after it, only before.
+        assert_eq!(
+            near("رب", "الرحمن", Proximity::FollowingAyahs(1))[..2],
+            [1, 2]
+        );
+        assert!(!near("الرحمن", "رب", Proximity::FollowingAyahs(1)).contains(&2));
+        assert!(near("الرحمن", "رب", Proximity::Ayahs(1)).contains(&2));
+
+        // Huge distances reach to the ends of the surah and no further.
+        let al_fatiha = |matches: Vec<u32>| matches.into_iter().filter(|&ayah| ayah < 7).count();
+        assert_eq!(
+            al_fatiha(near("بسم", "الضالين", Proximity::Words(u32::MAX))),
+            2
+        );
+        assert_eq!(
+            al_fatiha(near("الضالين", "بسم", Proximity::Ayahs(u32::MAX))),
+            2
+        );
+        assert_eq!(
+            al_fatiha(near("بسم", "الضالين", Proximity::FollowingAyahs(u32::MAX))),
+            2
+        );
+        assert_eq!(
+            al_fatiha(near("الضالين", "الم", Proximity::Words(u32::MAX))),
+            0
+        );
+
+        // A phrase with no terms matches nothing.
+        assert!(near("", "الله", Proximity::Words(u32::MAX)).is_empty());
+        assert!(near("الله", "؟", Proximity::Ayahs(u32::MAX)).is_empty());
+    }
+}
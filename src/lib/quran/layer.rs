@@ -0,0 +1,61 @@
+use std::{borrow::Cow, fmt, str::FromStr};
+
+/// A named rendering of the ayah text.
+///
+/// `Uthmani` is the script of the bundled text, `Simple` the Imlaei spelling
+/// Tanzil distributes, and `SimpleClean` the loaded text with every mark
+/// stripped, which is always available. A `Translation` layer holds a
+/// translation rather than Arabic; the English translation of the bundled
+/// text is `Translation("en")`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TextLayer {
+    #[default]
+    Uthmani,
+    Simple,
+    SimpleClean,
+    /// A translation, by language tag.
+    Translation(String),
+    Custom(String),
+}
+
+impl TextLayer {
+    pub fn name(&self) -> Cow<'_, str> {
+        match self {
+            Self::Uthmani => "uthmani".into(),
+            Self::Simple => "simple".into(),
+            Self::SimpleClean => "simple-clean".into(),
+            Self::Translation(language) => format!("translation-{}", language).into(),
+            Self::Custom(name) => name.into(),
+        }
+    }
+
+    /// The language of a translation layer.
+    pub fn translation_language(&self) -> Option<&str> {
+        match self {
+            Self::Translation(language) => Some(language),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TextLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for TextLayer {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "uthmani" => Self::Uthmani,
+            "simple" => Self::Simple,
+            "simple-clean" => Self::SimpleClean,
+            name => match name.strip_prefix("translation-") {
+                Some(language) => Self::Translation(language.to_string()),
+                None => Self::Custom(name.to_string()),
+            },
+        })
+    }
+}
@@ -0,0 +1,67 @@
+/// How a search term is compared with the words of an ayah.
+///
+/// A term of several words matches consecutive words of the ayah, each
+/// compared under the same mode.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MatchMode {
+    /// Whole words only: "علم" finds عَلِمَ but not ٱلۡعَٰلَمِينَ.
+    #[default]
+    Exact,
+    /// Words starting with the term.
+    Prefix,
+    /// Words ending with the term.
+    Suffix,
+    /// The term anywhere in the text, across word boundaries.
+    Substring,
+    /// Words whose segmented stem equals the term's, so that "الله" also
+    /// finds "لِلَّهِ" and "بِٱللَّهِ".
+    Stem,
+}
+
+impl MatchMode {
+    /// Compares one normalized word of the text with one of the term.
+    pub(in crate::quran) fn word_matches(self, word: &str, term: &str) -> bool {
+        match self {
+            MatchMode::Exact | MatchMode::Stem => word == term,
+            MatchMode::Prefix => word.starts_with(term),
+            MatchMode::Suffix => word.ends_with(term),
+            MatchMode::Substring => word.contains(term),
+        }
+    }
+}
+
+/// Whether `terms` match a run of consecutive `words` under `mode`.
+pub(in crate::quran) fn matches_words<S, T>(words: &[S], terms: &[T], mode: MatchMode) -> bool
+where
+    S: AsRef<str>,
+    T: AsRef<str>,
+{
+    if terms.is_empty() {
+        return false;
+    }
+
+    words.windows(terms.len()).any(|window| {
+        window
+            .iter()
+            .zip(terms)
+            .all(|(word, term)| mode.word_matches(word.as_ref(), term.as_ref()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_modes() {
+        let words = ["رب", "العلمين", "يعلمون"];
+
+        assert!(!matches_words(&words, &["علم"], MatchMode::Exact));
+        assert!(matches_words(&words, &["يعلم"], MatchMode::Prefix));
+        assert!(matches_words(&words, &["لمين"], MatchMode::Suffix));
+        assert!(matches_words(&words, &["علم"], MatchMode::Substring));
+        assert!(matches_words(&words, &["رب", "العلمين"], MatchMode::Exact));
+        assert!(!matches_words(&words, &["العلمين", "رب"], MatchMode::Exact));
+        assert!(!matches_words::<_, &str>(&words, &[], MatchMode::Exact));
+    }
+}
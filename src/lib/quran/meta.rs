@@ -0,0 +1,1091 @@
+use super::surah::RevelationType;
+
+/// Fixed facts about a surah that do not depend on the text edition.
+#[derive(Debug, Clone, Copy)]
+pub struct SurahInfo {
+    pub id: u32,
+    pub name: &'static str,
+    pub transliteration: &'static str,
+    pub translation: &'static str,
+    pub revelation_type: RevelationType,
+    /// Position in the order of revelation, from 1 (Al-'Alaq) to 114
+    /// (An-Nasr), in the standard Egyptian (al-Azhar) chronology.
+    pub revelation_order: u32,
+    /// Verse count in the Kufan numbering used by the Hafs text.
+    pub total_verses: u32,
+}
+
+/// Looks up the metadata of surah `id` (1-based).
+pub fn surah_info(id: u32) -> Option<&'static SurahInfo> {
+    SURAHS.get((id as usize).checked_sub(1)?)
+}
+
+/// The disjoined letters (al-huruf al-muqatta'at) a surah opens with, if any.
+pub fn muqattaat(id: u32) -> Option<&'static str> {
+    MUQATTAAT
+        .iter()
+        .find(|(surah, _)| *surah == id)
+        .map(|(_, letters)| *letters)
+}
+
+/// The 29 surahs that open with disjoined letters, and the letters.
+pub static MUQATTAAT: [(u32, &str); 29] = [
+    (2, "الم"),
+    (3, "الم"),
+    (7, "المص"),
+    (10, "الر"),
+    (11, "الر"),
+    (12, "الر"),
+    (13, "المر"),
+    (14, "الر"),
+    (15, "الر"),
+    (19, "كهيعص"),
+    (20, "طه"),
+    (26, "طسم"),
+    (27, "طس"),
+    (28, "طسم"),
+    (29, "الم"),
+    (30, "الم"),
+    (31, "الم"),
+    (32, "الم"),
+    (36, "يس"),
+    (38, "ص"),
+    (40, "حم"),
+    (41, "حم"),
+    (42, "حم عسق"),
+    (43, "حم"),
+    (44, "حم"),
+    (45, "حم"),
+    (46, "حم"),
+    (50, "ق"),
+    (68, "ن"),
+];
+
+pub static SURAHS: [SurahInfo; 114] = [
+    SurahInfo {
+        id: 1,
+        name: "الفاتحة",
+        transliteration: "Al-Fatihah",
+        translation: "The Opener",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 5,
+        total_verses: 7,
+    },
+    SurahInfo {
+        id: 2,
+        name: "البقرة",
+        transliteration: "Al-Baqarah",
+        translation: "The Cow",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 87,
+        total_verses: 286,
+    },
+    SurahInfo {
+        id: 3,
+        name: "آل عمران",
+        transliteration: "Ali 'Imran",
+        translation: "Family of Imran",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 89,
+        total_verses: 200,
+    },
+    SurahInfo {
+        id: 4,
+        name: "النساء",
+        transliteration: "An-Nisa",
+        translation: "The Women",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 92,
+        total_verses: 176,
+    },
+    SurahInfo {
+        id: 5,
+        name: "المائدة",
+        transliteration: "Al-Ma'idah",
+        translation: "The Table Spread",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 112,
+        total_verses: 120,
+    },
+    SurahInfo {
+        id: 6,
+        name: "الأنعام",
+        transliteration: "Al-An'am",
+        translation: "The Cattle",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 55,
+        total_verses: 165,
+    },
+    SurahInfo {
+        id: 7,
+        name: "الأعراف",
+        transliteration: "Al-A'raf",
+        translation: "The Heights",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 39,
+        total_verses: 206,
+    },
+    SurahInfo {
+        id: 8,
+        name: "الأنفال",
+        transliteration: "Al-Anfal",
+        translation: "The Spoils of War",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 88,
+        total_verses: 75,
+    },
+    SurahInfo {
+        id: 9,
+        name: "التوبة",
+        transliteration: "At-Tawbah",
+        translation: "The Repentance",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 113,
+        total_verses: 129,
+    },
+    SurahInfo {
+        id: 10,
+        name: "يونس",
+        transliteration: "Yunus",
+        translation: "Jonah",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 51,
+        total_verses: 109,
+    },
+    SurahInfo {
+        id: 11,
+        name: "هود",
+        transliteration: "Hud",
+        translation: "Hud",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 52,
+        total_verses: 123,
+    },
+    SurahInfo {
+        id: 12,
+        name: "يوسف",
+        transliteration: "Yusuf",
+        translation: "Joseph",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 53,
+        total_verses: 111,
+    },
+    SurahInfo {
+        id: 13,
+        name: "الرعد",
+        transliteration: "Ar-Ra'd",
+        translation: "The Thunder",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 96,
+        total_verses: 43,
+    },
+    SurahInfo {
+        id: 14,
+        name: "ابراهيم",
+        transliteration: "Ibrahim",
+        translation: "Abraham",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 72,
+        total_verses: 52,
+    },
+    SurahInfo {
+        id: 15,
+        name: "الحجر",
+        transliteration: "Al-Hijr",
+        translation: "The Rocky Tract",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 54,
+        total_verses: 99,
+    },
+    SurahInfo {
+        id: 16,
+        name: "النحل",
+        transliteration: "An-Nahl",
+        translation: "The Bee",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 70,
+        total_verses: 128,
+    },
+    SurahInfo {
+        id: 17,
+        name: "الإسراء",
+        transliteration: "Al-Isra",
+        translation: "The Night Journey",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 50,
+        total_verses: 111,
+    },
+    SurahInfo {
+        id: 18,
+        name: "الكهف",
+        transliteration: "Al-Kahf",
+        translation: "The Cave",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 69,
+        total_verses: 110,
+    },
+    SurahInfo {
+        id: 19,
+        name: "مريم",
+        transliteration: "Maryam",
+        translation: "Mary",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 44,
+        total_verses: 98,
+    },
+    SurahInfo {
+        id: 20,
+        name: "طه",
+        transliteration: "Taha",
+        translation: "Ta-Ha",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 45,
+        total_verses: 135,
+    },
+    SurahInfo {
+        id: 21,
+        name: "الأنبياء",
+        transliteration: "Al-Anbya",
+        translation: "The Prophets",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 73,
+        total_verses: 112,
+    },
+    SurahInfo {
+        id: 22,
+        name: "الحج",
+        transliteration: "Al-Hajj",
+        translation: "The Pilgrimage",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 103,
+        total_verses: 78,
+    },
+    SurahInfo {
+        id: 23,
+        name: "المؤمنون",
+        transliteration: "Al-Mu'minun",
+        translation: "The Believers",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 74,
+        total_verses: 118,
+    },
+    SurahInfo {
+        id: 24,
+        name: "النور",
+        transliteration: "An-Nur",
+        translation: "The Light",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 102,
+        total_verses: 64,
+    },
+    SurahInfo {
+        id: 25,
+        name: "الفرقان",
+        transliteration: "Al-Furqan",
+        translation: "The Criterion",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 42,
+        total_verses: 77,
+    },
+    SurahInfo {
+        id: 26,
+        name: "الشعراء",
+        transliteration: "Ash-Shu'ara",
+        translation: "The Poets",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 47,
+        total_verses: 227,
+    },
+    SurahInfo {
+        id: 27,
+        name: "النمل",
+        transliteration: "An-Naml",
+        translation: "The Ant",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 48,
+        total_verses: 93,
+    },
+    SurahInfo {
+        id: 28,
+        name: "القصص",
+        transliteration: "Al-Qasas",
+        translation: "The Stories",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 49,
+        total_verses: 88,
+    },
+    SurahInfo {
+        id: 29,
+        name: "العنكبوت",
+        transliteration: "Al-'Ankabut",
+        translation: "The Spider",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 85,
+        total_verses: 69,
+    },
+    SurahInfo {
+        id: 30,
+        name: "الروم",
+        transliteration: "Ar-Rum",
+        translation: "The Romans",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 84,
+        total_verses: 60,
+    },
+    SurahInfo {
+        id: 31,
+        name: "لقمان",
+        transliteration: "Luqman",
+        translation: "Luqman",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 57,
+        total_verses: 34,
+    },
+    SurahInfo {
+        id: 32,
+        name: "السجدة",
+        transliteration: "As-Sajdah",
+        translation: "The Prostration",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 75,
+        total_verses: 30,
+    },
+    SurahInfo {
+        id: 33,
+        name: "الأحزاب",
+        transliteration: "Al-Ahzab",
+        translation: "The Combined Forces",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 90,
+        total_verses: 73,
+    },
+    SurahInfo {
+        id: 34,
+        name: "سبإ",
+        transliteration: "Saba",
+        translation: "Sheba",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 58,
+        total_verses: 54,
+    },
+    SurahInfo {
+        id: 35,
+        name: "فاطر",
+        transliteration: "Fatir",
+        translation: "Originator",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 43,
+        total_verses: 45,
+    },
+    SurahInfo {
+        id: 36,
+        name: "يس",
+        transliteration: "Ya-Sin",
+        translation: "Ya Sin",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 41,
+        total_verses: 83,
+    },
+    SurahInfo {
+        id: 37,
+        name: "الصافات",
+        transliteration: "As-Saffat",
+        translation: "Those who set the Ranks",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 56,
+        total_verses: 182,
+    },
+    SurahInfo {
+        id: 38,
+        name: "ص",
+        transliteration: "Sad",
+        translation: "The Letter \"Saad\"",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 38,
+        total_verses: 88,
+    },
+    SurahInfo {
+        id: 39,
+        name: "الزمر",
+        transliteration: "Az-Zumar",
+        translation: "The Troops",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 59,
+        total_verses: 75,
+    },
+    SurahInfo {
+        id: 40,
+        name: "غافر",
+        transliteration: "Ghafir",
+        translation: "The Forgiver",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 60,
+        total_verses: 85,
+    },
+    SurahInfo {
+        id: 41,
+        name: "فصلت",
+        transliteration: "Fussilat",
+        translation: "Explained in Detail",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 61,
+        total_verses: 54,
+    },
+    SurahInfo {
+        id: 42,
+        name: "الشورى",
+        transliteration: "Ash-Shuraa",
+        translation: "The Consultation",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 62,
+        total_verses: 53,
+    },
+    SurahInfo {
+        id: 43,
+        name: "الزخرف",
+        transliteration: "Az-Zukhruf",
+        translation: "The Ornaments of Gold",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 63,
+        total_verses: 89,
+    },
+    SurahInfo {
+        id: 44,
+        name: "الدخان",
+        transliteration: "Ad-Dukhan",
+        translation: "The Smoke",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 64,
+        total_verses: 59,
+    },
+    SurahInfo {
+        id: 45,
+        name: "الجاثية",
+        transliteration: "Al-Jathiyah",
+        translation: "The Crouching",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 65,
+        total_verses: 37,
+    },
+    SurahInfo {
+        id: 46,
+        name: "الأحقاف",
+        transliteration: "Al-Ahqaf",
+        translation: "The Wind-Curved Sandhills",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 66,
+        total_verses: 35,
+    },
+    SurahInfo {
+        id: 47,
+        name: "محمد",
+        transliteration: "Muhammad",
+        translation: "Muhammad",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 95,
+        total_verses: 38,
+    },
+    SurahInfo {
+        id: 48,
+        name: "الفتح",
+        transliteration: "Al-Fath",
+        translation: "The Victory",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 111,
+        total_verses: 29,
+    },
+    SurahInfo {
+        id: 49,
+        name: "الحجرات",
+        transliteration: "Al-Hujurat",
+        translation: "The Rooms",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 106,
+        total_verses: 18,
+    },
+    SurahInfo {
+        id: 50,
+        name: "ق",
+        transliteration: "Qaf",
+        translation: "The Letter \"Qaf\"",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 34,
+        total_verses: 45,
+    },
+    SurahInfo {
+        id: 51,
+        name: "الذاريات",
+        transliteration: "Adh-Dhariyat",
+        translation: "The Winnowing Winds",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 67,
+        total_verses: 60,
+    },
+    SurahInfo {
+        id: 52,
+        name: "الطور",
+        transliteration: "At-Tur",
+        translation: "The Mount",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 76,
+        total_verses: 49,
+    },
+    SurahInfo {
+        id: 53,
+        name: "النجم",
+        transliteration: "An-Najm",
+        translation: "The Star",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 23,
+        total_verses: 62,
+    },
+    SurahInfo {
+        id: 54,
+        name: "القمر",
+        transliteration: "Al-Qamar",
+        translation: "The Moon",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 37,
+        total_verses: 55,
+    },
+    SurahInfo {
+        id: 55,
+        name: "الرحمن",
+        transliteration: "Ar-Rahman",
+        translation: "The Beneficent",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 97,
+        total_verses: 78,
+    },
+    SurahInfo {
+        id: 56,
+        name: "الواقعة",
+        transliteration: "Al-Waqi'ah",
+        translation: "The Inevitable",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 46,
+        total_verses: 96,
+    },
+    SurahInfo {
+        id: 57,
+        name: "الحديد",
+        transliteration: "Al-Hadid",
+        translation: "The Iron",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 94,
+        total_verses: 29,
+    },
+    SurahInfo {
+        id: 58,
+        name: "المجادلة",
+        transliteration: "Al-Mujadila",
+        translation: "The Pleading Woman",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 105,
+        total_verses: 22,
+    },
+    SurahInfo {
+        id: 59,
+        name: "الحشر",
+        transliteration: "Al-Hashr",
+        translation: "The Exile",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 101,
+        total_verses: 24,
+    },
+    SurahInfo {
+        id: 60,
+        name: "الممتحنة",
+        transliteration: "Al-Mumtahanah",
+        translation: "She that is to be examined",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 91,
+        total_verses: 13,
+    },
+    SurahInfo {
+        id: 61,
+        name: "الصف",
+        transliteration: "As-Saf",
+        translation: "The Ranks",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 109,
+        total_verses: 14,
+    },
+    SurahInfo {
+        id: 62,
+        name: "الجمعة",
+        transliteration: "Al-Jumu'ah",
+        translation: "The Congregation, Friday",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 110,
+        total_verses: 11,
+    },
+    SurahInfo {
+        id: 63,
+        name: "المنافقون",
+        transliteration: "Al-Munafiqun",
+        translation: "The Hypocrites",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 104,
+        total_verses: 11,
+    },
+    SurahInfo {
+        id: 64,
+        name: "التغابن",
+        transliteration: "At-Taghabun",
+        translation: "The Mutual Disillusion",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 108,
+        total_verses: 18,
+    },
+    SurahInfo {
+        id: 65,
+        name: "الطلاق",
+        transliteration: "At-Talaq",
+        translation: "The Divorce",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 99,
+        total_verses: 12,
+    },
+    SurahInfo {
+        id: 66,
+        name: "التحريم",
+        transliteration: "At-Tahrim",
+        translation: "The Prohibition",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 107,
+        total_verses: 12,
+    },
+    SurahInfo {
+        id: 67,
+        name: "الملك",
+        transliteration: "Al-Mulk",
+        translation: "The Sovereignty",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 77,
+        total_verses: 30,
+    },
+    SurahInfo {
+        id: 68,
+        name: "القلم",
+        transliteration: "Al-Qalam",
+        translation: "The Pen",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 2,
+        total_verses: 52,
+    },
+    SurahInfo {
+        id: 69,
+        name: "الحاقة",
+        transliteration: "Al-Haqqah",
+        translation: "The Reality",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 78,
+        total_verses: 52,
+    },
+    SurahInfo {
+        id: 70,
+        name: "المعارج",
+        transliteration: "Al-Ma'arij",
+        translation: "The Ascending Stairways",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 79,
+        total_verses: 44,
+    },
+    SurahInfo {
+        id: 71,
+        name: "نوح",
+        transliteration: "Nuh",
+        translation: "Noah",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 71,
+        total_verses: 28,
+    },
+    SurahInfo {
+        id: 72,
+        name: "الجن",
+        transliteration: "Al-Jinn",
+        translation: "The Jinn",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 40,
+        total_verses: 28,
+    },
+    SurahInfo {
+        id: 73,
+        name: "المزمل",
+        transliteration: "Al-Muzzammil",
+        translation: "The Enshrouded One",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 3,
+        total_verses: 20,
+    },
+    SurahInfo {
+        id: 74,
+        name: "المدثر",
+        transliteration: "Al-Muddaththir",
+        translation: "The Cloaked One",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 4,
+        total_verses: 56,
+    },
+    SurahInfo {
+        id: 75,
+        name: "القيامة",
+        transliteration: "Al-Qiyamah",
+        translation: "The Resurrection",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 31,
+        total_verses: 40,
+    },
+    SurahInfo {
+        id: 76,
+        name: "الانسان",
+        transliteration: "Al-Insan",
+        translation: "The Man",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 98,
+        total_verses: 31,
+    },
+    SurahInfo {
+        id: 77,
+        name: "المرسلات",
+        transliteration: "Al-Mursalat",
+        translation: "The Emissaries",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 33,
+        total_verses: 50,
+    },
+    SurahInfo {
+        id: 78,
+        name: "النبإ",
+        transliteration: "An-Naba",
+        translation: "The Tidings",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 80,
+        total_verses: 40,
+    },
+    SurahInfo {
+        id: 79,
+        name: "النازعات",
+        transliteration: "An-Nazi'at",
+        translation: "Those who drag forth",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 81,
+        total_verses: 46,
+    },
+    SurahInfo {
+        id: 80,
+        name: "عبس",
+        transliteration: "'Abasa",
+        translation: "He Frowned",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 24,
+        total_verses: 42,
+    },
+    SurahInfo {
+        id: 81,
+        name: "التكوير",
+        transliteration: "At-Takwir",
+        translation: "The Overthrowing",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 7,
+        total_verses: 29,
+    },
+    SurahInfo {
+        id: 82,
+        name: "الإنفطار",
+        transliteration: "Al-Infitar",
+        translation: "The Cleaving",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 82,
+        total_verses: 19,
+    },
+    SurahInfo {
+        id: 83,
+        name: "المطففين",
+        transliteration: "Al-Mutaffifin",
+        translation: "The Defrauding",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 86,
+        total_verses: 36,
+    },
+    SurahInfo {
+        id: 84,
+        name: "الإنشقاق",
+        transliteration: "Al-Inshiqaq",
+        translation: "The Sundering",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 83,
+        total_verses: 25,
+    },
+    SurahInfo {
+        id: 85,
+        name: "البروج",
+        transliteration: "Al-Buruj",
+        translation: "The Mansions of the Stars",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 27,
+        total_verses: 22,
+    },
+    SurahInfo {
+        id: 86,
+        name: "الطارق",
+        transliteration: "At-Tariq",
+        translation: "The Nightcommer",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 36,
+        total_verses: 17,
+    },
+    SurahInfo {
+        id: 87,
+        name: "الأعلى",
+        transliteration: "Al-A'la",
+        translation: "The Most High",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 8,
+        total_verses: 19,
+    },
+    SurahInfo {
+        id: 88,
+        name: "الغاشية",
+        transliteration: "Al-Ghashiyah",
+        translation: "The Overwhelming",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 68,
+        total_verses: 26,
+    },
+    SurahInfo {
+        id: 89,
+        name: "الفجر",
+        transliteration: "Al-Fajr",
+        translation: "The Dawn",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 10,
+        total_verses: 30,
+    },
+    SurahInfo {
+        id: 90,
+        name: "البلد",
+        transliteration: "Al-Balad",
+        translation: "The City",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 35,
+        total_verses: 20,
+    },
+    SurahInfo {
+        id: 91,
+        name: "الشمس",
+        transliteration: "Ash-Shams",
+        translation: "The Sun",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 26,
+        total_verses: 15,
+    },
+    SurahInfo {
+        id: 92,
+        name: "الليل",
+        transliteration: "Al-Layl",
+        translation: "The Night",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 9,
+        total_verses: 21,
+    },
+    SurahInfo {
+        id: 93,
+        name: "الضحى",
+        transliteration: "Ad-Duhaa",
+        translation: "The Morning Hours",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 11,
+        total_verses: 11,
+    },
+    SurahInfo {
+        id: 94,
+        name: "الشرح",
+        transliteration: "Ash-Sharh",
+        translation: "The Relief",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 12,
+        total_verses: 8,
+    },
+    SurahInfo {
+        id: 95,
+        name: "التين",
+        transliteration: "At-Tin",
+        translation: "The Fig",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 28,
+        total_verses: 8,
+    },
+    SurahInfo {
+        id: 96,
+        name: "العلق",
+        transliteration: "Al-'Alaq",
+        translation: "The Clot",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 1,
+        total_verses: 19,
+    },
+    SurahInfo {
+        id: 97,
+        name: "القدر",
+        transliteration: "Al-Qadr",
+        translation: "The Power",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 25,
+        total_verses: 5,
+    },
+    SurahInfo {
+        id: 98,
+        name: "البينة",
+        transliteration: "Al-Bayyinah",
+        translation: "The Clear Proof",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 100,
+        total_verses: 8,
+    },
+    SurahInfo {
+        id: 99,
+        name: "الزلزلة",
+        transliteration: "Az-Zalzalah",
+        translation: "The Earthquake",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 93,
+        total_verses: 8,
+    },
+    SurahInfo {
+        id: 100,
+        name: "العاديات",
+        transliteration: "Al-'Adiyat",
+        translation: "The Courser",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 14,
+        total_verses: 11,
+    },
+    SurahInfo {
+        id: 101,
+        name: "القارعة",
+        transliteration: "Al-Qari'ah",
+        translation: "The Calamity",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 30,
+        total_verses: 11,
+    },
+    SurahInfo {
+        id: 102,
+        name: "التكاثر",
+        transliteration: "At-Takathur",
+        translation: "The Rivalry in world increase",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 16,
+        total_verses: 8,
+    },
+    SurahInfo {
+        id: 103,
+        name: "العصر",
+        transliteration: "Al-'Asr",
+        translation: "The Declining Day",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 13,
+        total_verses: 3,
+    },
+    SurahInfo {
+        id: 104,
+        name: "الهمزة",
+        transliteration: "Al-Humazah",
+        translation: "The Traducer",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 32,
+        total_verses: 9,
+    },
+    SurahInfo {
+        id: 105,
+        name: "الفيل",
+        transliteration: "Al-Fil",
+        translation: "The Elephant",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 19,
+        total_verses: 5,
+    },
+    SurahInfo {
+        id: 106,
+        name: "قريش",
+        transliteration: "Quraysh",
+        translation: "Quraysh",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 29,
+        total_verses: 4,
+    },
+    SurahInfo {
+        id: 107,
+        name: "الماعون",
+        transliteration: "Al-Ma'un",
+        translation: "The Small kindnesses",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 17,
+        total_verses: 7,
+    },
+    SurahInfo {
+        id: 108,
+        name: "الكوثر",
+        transliteration: "Al-Kawthar",
+        translation: "The Abundance",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 15,
+        total_verses: 3,
+    },
+    SurahInfo {
+        id: 109,
+        name: "الكافرون",
+        transliteration: "Al-Kafirun",
+        translation: "The Disbelievers",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 18,
+        total_verses: 6,
+    },
+    SurahInfo {
+        id: 110,
+        name: "النصر",
+        transliteration: "An-Nasr",
+        translation: "The Divine Support",
+        revelation_type: RevelationType::Medinan,
+        revelation_order: 114,
+        total_verses: 3,
+    },
+    SurahInfo {
+        id: 111,
+        name: "المسد",
+        transliteration: "Al-Masad",
+        translation: "The Palm Fiber",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 6,
+        total_verses: 5,
+    },
+    SurahInfo {
+        id: 112,
+        name: "الإخلاص",
+        transliteration: "Al-Ikhlas",
+        translation: "The Sincerity",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 22,
+        total_verses: 4,
+    },
+    SurahInfo {
+        id: 113,
+        name: "الفلق",
+        transliteration: "Al-Falaq",
+        translation: "The Daybreak",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 20,
+        total_verses: 5,
+    },
+    SurahInfo {
+        id: 114,
+        name: "الناس",
+        transliteration: "An-Nas",
+        translation: "Mankind",
+        revelation_type: RevelationType::Meccan,
+        revelation_order: 21,
+        total_verses: 6,
+    },
+];
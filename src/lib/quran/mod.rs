@@ -1,39 +1,374 @@
 pub mod analyze;
 pub mod concordance;
+pub mod division;
+pub mod fuzzy;
+pub mod hit;
+pub mod index;
+pub mod layer;
+pub mod matching;
+pub mod meta;
+pub mod pattern;
+pub mod query;
+pub mod rank;
+pub mod reference;
 pub mod search;
+pub mod source;
 pub mod surah;
 pub mod verse;
 
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs::File,
+    io::Read,
+    path::Path,
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+
 use surah::Surah;
 
-use crate::error::QuranError;
+use crate::{
+    error::QuranError,
+    morphology::{MorphWord, Morphology},
+    normalizer::Normalizer,
+    remove_diacritics,
+};
 
-use self::verse::Ayah;
+use self::{
+    division::{Division, Sajdah, SAJDAHS},
+    index::InvertedIndex,
+    layer::TextLayer,
+    reference::VerseRange,
+    source::{CorpusSource, JsonSource},
+    verse::Ayah,
+};
 
 const QURAN_FILE_PATH: &str = "../../data/quran.json";
 
 pub struct Quran {
     surahs: Vec<Surah>,
+    primary_layer: TextLayer,
+    morphology: Option<Morphology>,
+    indexes: RwLock<HashMap<(TextLayer, Normalizer), Arc<InvertedIndex>>>,
 }
 
 impl Quran {
+    /// Loads the text bundled with the crate.
     pub fn new() -> Result<Self, QuranError> {
         let str_value = include_str!("../../data/quran.json");
-        let json_value: serde_json::Value = serde_json::from_str(str_value)
-            .map_err(|e| QuranError::JsonError(format!("{}: {}", QURAN_FILE_PATH, e)))?;
-        let surahs: Vec<Surah> = Self::parse_surahs(json_value)?;
+        Self::parse(&JsonSource, str_value, QURAN_FILE_PATH)
+    }
+
+    /// Loads a text in the `quran.json` layout from a file on disk.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, QuranError> {
+        Self::from_source_path(&JsonSource, path)
+    }
 
-        Ok(Self { surahs })
+    /// Loads a text in the `quran.json` layout from any reader.
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, QuranError> {
+        Self::from_source_reader(&JsonSource, reader)
+    }
+
+    /// Loads a file on disk in the format understood by `source`.
+    pub fn from_source_path<S, P>(source: &S, path: P) -> Result<Self, QuranError>
+    where
+        S: CorpusSource + ?Sized,
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let mut file = File::open(path)
+            .map_err(|e| QuranError::FileOpenError(format!("{}: {}", path.display(), e)))?;
+        let mut str_value = String::new();
+        file.read_to_string(&mut str_value)
+            .map_err(|e| QuranError::FileOpenError(format!("{}: {}", path.display(), e)))?;
+
+        Self::parse(source, &str_value, &path.display().to_string())
+    }
+
+    /// Loads the contents of `reader` in the format understood by `source`.
+    pub fn from_source_reader<S, R>(source: &S, mut reader: R) -> Result<Self, QuranError>
+    where
+        S: CorpusSource + ?Sized,
+        R: Read,
+    {
+        let mut str_value = String::new();
+        reader
+            .read_to_string(&mut str_value)
+            .map_err(|e| QuranError::FileOpenError(format!("<reader>: {}", e)))?;
+
+        Self::parse(source, &str_value, "<reader>")
+    }
+
+    /// Parses `input` in the format understood by `source`.
+    pub fn from_source_str<S>(source: &S, input: &str) -> Result<Self, QuranError>
+    where
+        S: CorpusSource + ?Sized,
+    {
+        Self::parse(source, input, "<string>")
+    }
+
+    fn parse<S>(source: &S, input: &str, origin: &str) -> Result<Self, QuranError>
+    where
+        S: CorpusSource + ?Sized,
+    {
+        let surahs = source.parse(input, origin)?;
+        let mut quran = Self {
+            surahs,
+            primary_layer: TextLayer::default(),
+            morphology: None,
+            indexes: RwLock::default(),
+        };
+        quran.derive_layer(TextLayer::SimpleClean, |ayah| {
+            remove_diacritics(ayah.text())
+        })?;
+        // Sources put translations straight into the layer; ayahs they left
+        // untranslated get an empty text so the layer is complete.
+        let english = TextLayer::Translation("en".to_string());
+        if quran.has_layer(&english) {
+            for ayah in quran.surahs.iter_mut().flat_map(|s| s.ayahs.iter_mut()) {
+                ayah.layers.entry(english.clone()).or_default();
+            }
+        }
+
+        Ok(quran)
+    }
+
+    /// The layer the text was loaded as; [`TextLayer::Uthmani`] unless changed
+    /// with [`Quran::with_primary_layer`].
+    pub fn primary_layer(&self) -> &TextLayer {
+        &self.primary_layer
+    }
+
+    /// Labels the loaded text as `layer`, e.g. when a Tanzil "simple" edition
+    /// was loaded instead of the Uthmani one.
+    ///
+    /// Fails with [`QuranError::LayerAttached`] when another text is already
+    /// attached as `layer`.
+    pub fn with_primary_layer(mut self, layer: TextLayer) -> Result<Self, QuranError> {
+        if layer != self.primary_layer && self.has_layer(&layer) {
+            return Err(QuranError::LayerAttached(layer.to_string()));
+        }
+        for ayah in self.surahs.iter_mut().flat_map(|s| s.ayahs.iter_mut()) {
+            ayah.layer = layer.clone();
+        }
+        self.primary_layer = layer;
+        self.clear_indexes();
+        Ok(self)
+    }
+
+    /// Every layer attached to the text, primary layer first.
+    pub fn layers(&self) -> Vec<TextLayer> {
+        let others: BTreeSet<&TextLayer> = self
+            .surahs
+            .iter()
+            .flat_map(|surah| &surah.ayahs)
+            .flat_map(|ayah| ayah.layers.keys())
+            .collect();
+        std::iter::once(&self.primary_layer)
+            .chain(others)
+            .cloned()
+            .collect()
+    }
+
+    pub fn has_layer(&self, layer: &TextLayer) -> bool {
+        *layer == self.primary_layer
+            || self
+                .surahs
+                .iter()
+                .flat_map(|surah| &surah.ayahs)
+                .any(|ayah| ayah.layers.contains_key(layer))
+    }
+
+    /// Fails with [`QuranError::UnknownLayer`] unless `layer` is attached.
+    pub fn check_layer(&self, layer: &TextLayer) -> Result<(), QuranError> {
+        if self.has_layer(layer) {
+            Ok(())
+        } else {
+            Err(QuranError::UnknownLayer(layer.to_string()))
+        }
+    }
+
+    /// Attaches another edition of the text, parsed by `source`, as `layer`.
+    ///
+    /// The edition must have the same surahs and verse counts as this one, and
+    /// `layer` must not be the primary layer.
+    pub fn add_layer<S>(
+        &mut self,
+        layer: TextLayer,
+        source: &S,
+        input: &str,
+    ) -> Result<(), QuranError>
+    where
+        S: CorpusSource + ?Sized,
+    {
+        self.check_secondary(&layer)?;
+        let other = source.parse(input, &layer.name())?;
+
+        if other.len() != self.surahs.len() {
+            return Err(QuranError::LayerMismatch {
+                layer: layer.to_string(),
+                surah: None,
+                expected: self.surahs.len(),
+                found: other.len(),
+            });
+        }
+
+        for (surah, other_surah) in self.surahs.iter().zip(&other) {
+            if surah.ayahs.len() != other_surah.ayahs.len() {
+                return Err(QuranError::LayerMismatch {
+                    layer: layer.to_string(),
+                    surah: Some(surah.id),
+                    expected: surah.ayahs.len(),
+                    found: other_surah.ayahs.len(),
+                });
+            }
+        }
+
+        let ayahs = self.surahs.iter_mut().flat_map(|s| s.ayahs.iter_mut());
+        let other_ayahs = other.into_iter().flat_map(|s| s.ayahs.into_iter());
+        for (ayah, other_ayah) in ayahs.zip(other_ayahs) {
+            ayah.layers.insert(layer.clone(), other_ayah.text);
+        }
+        self.clear_indexes();
+
+        Ok(())
+    }
+
+    /// Reads a file with `source` and attaches it as `layer`.
+    pub fn add_layer_path<S, P>(
+        &mut self,
+        layer: TextLayer,
+        source: &S,
+        path: P,
+    ) -> Result<(), QuranError>
+    where
+        S: CorpusSource + ?Sized,
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let input = std::fs::read_to_string(path)
+            .map_err(|e| QuranError::FileOpenError(format!("{}: {}", path.display(), e)))?;
+        self.add_layer(layer, source, &input)
+    }
+
+    /// Attaches `layer`, computing each ayah's text from the ayah itself.
+    /// Fails with [`QuranError::PrimaryLayer`] for the primary layer.
+    pub fn derive_layer<F>(&mut self, layer: TextLayer, derive: F) -> Result<(), QuranError>
+    where
+        F: Fn(&Ayah) -> String,
+    {
+        self.check_secondary(&layer)?;
+        for ayah in self.surahs.iter_mut().flat_map(|s| s.ayahs.iter_mut()) {
+            let text = derive(ayah);
+            ayah.layers.insert(layer.clone(), text);
+        }
+        self.clear_indexes();
+        Ok(())
+    }
+
+    /// Text in the primary layer is the loaded text itself, so that layer
+    /// cannot be attached again.
+    fn check_secondary(&self, layer: &TextLayer) -> Result<(), QuranError> {
+        if *layer == self.primary_layer {
+            Err(QuranError::PrimaryLayer(layer.to_string()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The inverted index of `layer` under `normalizer`, built on first use
+    /// and kept until the layers change.
+    pub fn index(&self, layer: &TextLayer, normalizer: &Normalizer) -> Arc<InvertedIndex> {
+        let key = (layer.clone(), normalizer.clone());
+        if let Some(index) = self.indexes.read().unwrap().get(&key) {
+            return Arc::clone(index);
+        }
+
+        let index = Arc::new(InvertedIndex::build(self, layer, normalizer));
+        Arc::clone(self.indexes.write().unwrap().entry(key).or_insert(index))
+    }
+
+    fn clear_indexes(&mut self) {
+        self.indexes.get_mut().unwrap().clear();
+    }
+
+    /// Attaches the Quranic Arabic Corpus morphology as an annotation layer.
+    ///
+    /// Words are aligned by position with the whitespace-separated words of the
+    /// Uthmani text; every annotated word must exist in this text.
+    pub fn attach_morphology(&mut self, morphology: Morphology) -> Result<(), QuranError> {
+        for word in morphology.words() {
+            let location = word.location;
+            let exists = self
+                .surahs
+                .get((location.surah as usize).wrapping_sub(1))
+                .and_then(|surah| surah.ayahs.get((location.ayah as usize).wrapping_sub(1)))
+                .is_some_and(|ayah| {
+                    location.word >= 1 && location.word as usize <= ayah.words().len()
+                });
+            if !exists {
+                return Err(QuranError::MorphologyMismatch {
+                    surah: location.surah,
+                    ayah: location.ayah,
+                    word: location.word,
+                });
+            }
+        }
+
+        self.morphology = Some(morphology);
+        Ok(())
+    }
+
+    pub fn morphology(&self) -> Option<&Morphology> {
+        self.morphology.as_ref()
+    }
+
+    /// The annotated words of `ayah`, or none without attached morphology.
+    pub fn ayah_morphology(&self, ayah: &Ayah) -> Vec<&MorphWord> {
+        self.morphology
+            .as_ref()
+            .map(|morphology| {
+                morphology
+                    .ayah_words(ayah.surah_id(), ayah.number() as u32)
+                    .collect()
+            })
+            .unwrap_or_default()
     }
 
     pub fn surahs(&self) -> &[Surah] {
         &self.surahs
     }
 
+    /// The surahs in the order of revelation; surahs without a place in it
+    /// come last, in text order.
+    pub fn surahs_in_revelation_order(&self) -> Vec<&Surah> {
+        let mut surahs: Vec<&Surah> = self.surahs.iter().collect();
+        surahs.sort_by_key(|surah| surah.revelation_order().unwrap_or(u32::MAX));
+        surahs
+    }
+
+    /// The surah at zero-based position `surah_number`. Panics when out of
+    /// range; [`Quran::get_surah`] takes the surah number instead and fails
+    /// with an error.
     pub fn surah(&self, surah_number: usize) -> &Surah {
         &self.surahs[surah_number]
     }
 
+    /// Surah number `surah_number`, counting from 1.
+    pub fn get_surah(&self, surah_number: usize) -> Result<&Surah, QuranError> {
+        self.surahs
+            .get(surah_number.wrapping_sub(1))
+            .ok_or(QuranError::SurahOutOfRange {
+                surah: surah_number as u32,
+                surahs: self.surahs.len(),
+            })
+    }
+
+    /// Ayah `ayah_number` of surah `surah_number`, both counting from 1.
+    pub fn get_ayah(&self, surah_number: usize, ayah_number: usize) -> Result<&Ayah, QuranError> {
+        self.get_surah(surah_number)?.get_ayah(ayah_number)
+    }
+
     pub fn ayas(&self) -> Vec<&Ayah> {
         self.surahs
             .iter()
@@ -43,76 +378,347 @@ impl Quran {
             .collect::<Vec<_>>()
     }
 
-    fn parse_surahs(json_value: serde_json::Value) -> Result<Vec<Surah>, QuranError> {
-        if let serde_json::Value::Array(surahs) = json_value {
-            let mut parsed_surahs = Vec::new();
-
-            for surah in surahs {
-                let id = surah["id"]
-                    .as_u64()
-                    .ok_or(QuranError::JsonError("Invalid surah id".to_string()))?
-                    as u32;
-
-                let surah_name = surah["name"]
-                    .as_str()
-                    .ok_or(QuranError::JsonError("Invalid surah name".to_string()))?
-                    .to_string();
-
-                let total_verses = surah["total_verses"]
-                    .as_u64()
-                    .ok_or(QuranError::JsonError("Invalid total verses".to_string()))?
-                    as u32;
-
-                let ayahs = surah["verses"]
-                    .as_array()
-                    .ok_or(QuranError::JsonError("Invalid verses".to_string()))?;
-
-                let mut parsed_ayahs = Vec::new();
-
-                for ayah in ayahs {
-                    let ayah_number = ayah["id"]
-                        .as_u64()
-                        .ok_or(QuranError::JsonError("Invalid ayah id".to_string()))?
-                        as u32;
-
-                    let ayah_text = ayah["text"]
-                        .as_str()
-                        .ok_or(QuranError::JsonError("Invalid ayah text".to_string()))?
-                        .to_string();
-
-                    let ayah = Ayah {
-                        ayah_number,
-                        surah_name: surah_name.clone(),
-                        text: ayah_text,
-                    };
-
-                    parsed_ayahs.push(ayah);
-                }
-
-                let surah = Surah {
-                    name: surah_name,
-                    id,
-                    total_verses,
-                    ayahs: parsed_ayahs,
-                };
-
-                parsed_surahs.push(surah);
-            }
-
-            Ok(parsed_surahs)
-        } else {
-            Err(QuranError::JsonError("Invalid JSON structure".to_string()))
+    /// The ayahs named by a list of references such as `3:190-194, 4:1`, in
+    /// the order given; see [`reference`] for the accepted forms.
+    pub fn verses(&self, references: &str) -> Result<Vec<&Ayah>, QuranError> {
+        let mut ayahs = Vec::new();
+        for range in VerseRange::parse_list(references)? {
+            ayahs.extend(range.resolve(self)?);
         }
+        Ok(ayahs)
+    }
+
+    /// The ayahs of division `number` of kind `division`, counting from 1.
+    pub fn division(&self, division: Division, number: u32) -> Result<Vec<&Ayah>, QuranError> {
+        division.range(number)?.resolve(self)
+    }
+
+    /// The ayahs of juz' `number`, from 1 to 30.
+    pub fn juz(&self, number: u32) -> Result<Vec<&Ayah>, QuranError> {
+        self.division(Division::Juz, number)
+    }
+
+    /// The ayahs of hizb `number`, from 1 to 60.
+    pub fn hizb(&self, number: u32) -> Result<Vec<&Ayah>, QuranError> {
+        self.division(Division::Hizb, number)
+    }
+
+    /// The ayahs of rub' al-hizb `number`, from 1 to 240.
+    pub fn rub(&self, number: u32) -> Result<Vec<&Ayah>, QuranError> {
+        self.division(Division::Rub, number)
+    }
+
+    /// The ayahs of manzil `number`, from 1 to 7.
+    pub fn manzil(&self, number: u32) -> Result<Vec<&Ayah>, QuranError> {
+        self.division(Division::Manzil, number)
+    }
+
+    /// The ayahs of ruku' `number`, counting through the whole text.
+    pub fn ruku(&self, number: u32) -> Result<Vec<&Ayah>, QuranError> {
+        self.division(Division::Ruku, number)
+    }
+
+    /// The ayahs on page `number` of the Madinah mushaf, from 1 to 604.
+    pub fn page(&self, number: u32) -> Result<Vec<&Ayah>, QuranError> {
+        self.division(Division::Page, number)
+    }
+
+    /// The ayahs of prostration, in order.
+    pub fn sajdahs(&self) -> &'static [Sajdah] {
+        &SAJDAHS
+    }
+}
+
+impl FromStr for Quran {
+    type Err = QuranError;
+
+    /// Parses a text in the `quran.json` layout held in memory.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_source_str(&JsonSource, s)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::quran::surah::RevelationType;
 
     #[test]
     fn test_new() {
         let quran = Quran::new().unwrap();
         assert_eq!(quran.surahs.len(), 114);
     }
+
+    #[test]
+    fn test_revelation_order() {
+        let quran = Quran::new().unwrap();
+        let order: Vec<u32> = quran
+            .surahs_in_revelation_order()
+            .iter()
+            .map(|surah| surah.id())
+            .collect();
+        assert_eq!(order[..6], [96, 68, 73, 74, 1, 111]);
+        assert_eq!(order[86], 2);
+        assert_eq!(order[113], 110);
+        assert_eq!(quran.surah(0).revelation_order(), Some(5));
+
+        let mut sorted = order.clone();
+        sorted.sort();
+        assert_eq!(sorted, (1..=114).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_metadata() {
+        let quran = Quran::new().unwrap();
+        let fatihah = quran.surah(0);
+        assert_eq!(fatihah.transliteration(), Some("Al-Fatihah"));
+        assert_eq!(fatihah.translation(), Some("The Opener"));
+        assert_eq!(fatihah.revelation_type(), Some(RevelationType::Meccan));
+        assert_eq!(
+            quran.surah(1).revelation_type(),
+            Some(RevelationType::Medinan)
+        );
+        assert_eq!(
+            fatihah.ayahs()[4].translation(),
+            Some("It is You we worship and You we ask for help")
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        let json = r#"[{"id": 1, "name": "الفاتحة", "total_verses": 1,
+            "verses": [{"id": 1, "text": "بِسۡمِ ٱللَّهِ"}]}]"#;
+        let quran = Quran::from_str(json).unwrap();
+        assert_eq!(quran.surahs.len(), 1);
+        assert_eq!(quran.surah(0).ayahs()[0].text(), "بِسۡمِ ٱللَّهِ");
+    }
+
+    #[test]
+    fn test_from_reader() {
+        let json = include_str!("../../data/quran.json");
+        let quran = Quran::from_reader(json.as_bytes()).unwrap();
+        assert_eq!(quran.surahs.len(), 114);
+    }
+
+    #[test]
+    fn test_schema_error_context() {
+        let json = r#"[{"id": 1, "name": "الفاتحة", "total_verses": 2,
+            "verses": [{"id": 1, "text": "بِسۡمِ ٱللَّهِ"}, {"id": 2, "text": 7}]}]"#;
+        match Quran::from_str(json) {
+            Err(QuranError::SchemaError {
+                path,
+                surah,
+                verse,
+                field,
+                ..
+            }) => {
+                assert_eq!(path, "[0].verses[1].text");
+                assert_eq!(surah, Some(0));
+                assert_eq!(verse, Some(1));
+                assert_eq!(field.as_deref(), Some("text"));
+            }
+            other => panic!("unexpected result: {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn test_schema_error_missing_field() {
+        let json = r#"[{"id": 1, "name": "الفاتحة", "total_verses": 1,
+            "verses": [{"text": "بِسۡمِ ٱللَّهِ"}]}]"#;
+        match Quran::from_str(json) {
+            Err(QuranError::SchemaError { verse, field, .. }) => {
+                assert_eq!(verse, Some(0));
+                assert_eq!(field.as_deref(), Some("id"));
+            }
+            other => panic!("unexpected result: {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn test_verse_count_mismatch() {
+        let json = r#"[{"id": 1, "name": "الفاتحة", "total_verses": 7,
+            "verses": [{"id": 1, "text": "بِسۡمِ ٱللَّهِ"}]}]"#;
+        assert!(matches!(
+            Quran::from_str(json),
+            Err(QuranError::VerseCountMismatch {
+                surah: 1,
+                expected: 7,
+                found: 1,
+                ..
+            })
+        ));
+        match Quran::from_str(json) {
+            Err(QuranError::VerseCountMismatch { origin, path, .. }) => {
+                assert_eq!(origin, "<string>");
+                assert_eq!(path, "[0].total_verses");
+            }
+            other => panic!("unexpected result: {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn test_non_contiguous_ids() {
+        let json = r#"[{"id": 1, "name": "الفاتحة", "total_verses": 2,
+            "verses": [{"id": 1, "text": "بِسۡمِ"}, {"id": 3, "text": "ٱللَّهِ"}]}]"#;
+        assert!(matches!(
+            Quran::from_str(json),
+            Err(QuranError::NonContiguousId {
+                surah: 1,
+                verse: Some(1),
+                expected: 2,
+                found: 3,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_checked_accessors() {
+        let quran = Quran::new().unwrap();
+        assert_eq!(quran.get_surah(114).unwrap().id(), 114);
+        assert_eq!(quran.get_ayah(2, 286).unwrap().number(), 286);
+
+        for surah in [0, 115] {
+            assert!(matches!(
+                quran.get_surah(surah),
+                Err(QuranError::SurahOutOfRange { surahs: 114, .. })
+            ));
+        }
+        for ayah in [0, 8] {
+            assert!(matches!(
+                quran.get_ayah(1, ayah),
+                Err(QuranError::AyahOutOfRange {
+                    surah: 1,
+                    ayahs: 7,
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_verses() {
+        let quran = Quran::new().unwrap();
+        let ayahs = quran.verses("3:190-194, 4:1").unwrap();
+        assert_eq!(ayahs.len(), 6);
+        assert_eq!((ayahs[5].surah_id(), ayahs[5].number()), (4, 1));
+
+        assert!(matches!(
+            quran.verses("2:300"),
+            Err(QuranError::AyahOutOfRange { ayahs: 286, .. })
+        ));
+    }
+
+    #[test]
+    fn test_layers() {
+        let mut quran = Quran::new().unwrap();
+        assert_eq!(
+            quran.layers(),
+            vec![
+                TextLayer::Uthmani,
+                TextLayer::SimpleClean,
+                TextLayer::Translation("en".to_string())
+            ]
+        );
+
+        let ayah = &quran.surah(0).ayahs()[0];
+        assert_eq!(
+            ayah.text_in(&TextLayer::SimpleClean),
+            Some("بسم الله الرحمن الرحيم")
+        );
+        assert_eq!(ayah.text_in(&TextLayer::Simple), None);
+        assert!(ayah.translation().is_some());
+        assert_eq!(
+            ayah.text_in(&TextLayer::Translation("en".to_string())),
+            ayah.translation()
+        );
+
+        let simple: String = quran
+            .surahs()
+            .iter()
+            .flat_map(|surah| {
+                surah.ayahs().iter().map(move |ayah| {
+                    format!(
+                        "{}|{}|simple {}\n",
+                        surah.id(),
+                        ayah.number(),
+                        ayah.number()
+                    )
+                })
+            })
+            .collect();
+        quran
+            .add_layer(TextLayer::Simple, &source::TanzilTextSource, &simple)
+            .unwrap();
+        assert_eq!(
+            quran.surah(1).ayahs()[4].text_in(&TextLayer::Simple),
+            Some("simple 5")
+        );
+
+        let too_short = "1|1|بسم الله";
+        assert!(matches!(
+            quran.add_layer(
+                TextLayer::Custom("x".to_string()),
+                &source::TanzilTextSource,
+                too_short
+            ),
+            Err(QuranError::LayerMismatch { .. })
+        ));
+
+        assert!(matches!(
+            quran.add_layer(TextLayer::Uthmani, &source::TanzilTextSource, &simple),
+            Err(QuranError::PrimaryLayer(_))
+        ));
+        assert!(matches!(
+            quran.derive_layer(TextLayer::Uthmani, |_| String::new()),
+            Err(QuranError::PrimaryLayer(_))
+        ));
+        assert!(quran.surah(0).ayahs()[0].text().starts_with("بِسۡمِ"));
+
+        quran
+            .derive_layer(TextLayer::Custom("x".to_string()), |ayah| {
+                ayah.number().to_string()
+            })
+            .unwrap();
+        assert_eq!(quran.layers().len(), 5);
+
+        assert!(matches!(
+            quran.with_primary_layer(TextLayer::Simple),
+            Err(QuranError::LayerAttached(_))
+        ));
+
+        let quran = Quran::new()
+            .unwrap()
+            .with_primary_layer(TextLayer::Simple)
+            .unwrap();
+        assert_eq!(quran.layers()[0], TextLayer::Simple);
+        assert!(!quran.has_layer(&TextLayer::Uthmani));
+        assert!(quran.surah(0).ayahs()[0]
+            .text_in(&TextLayer::Simple)
+            .is_some_and(|text| text.starts_with("بِسۡمِ")));
+    }
+
+    #[test]
+    fn test_attach_morphology() {
+        let mut quran = Quran::new().unwrap();
+        for (location, word) in [("1:1:5", 5), ("1:8:1", 1)] {
+            let morphology =
+                Morphology::parse(&format!("({}:1)\tbi\tP\tPREFIX", location)).unwrap();
+            assert!(matches!(
+                quran.attach_morphology(morphology),
+                Err(QuranError::MorphologyMismatch { word: w, .. }) if w == word
+            ));
+        }
+        assert!(quran.morphology().is_none());
+
+        let morphology = Morphology::parse("(1:1:4:1)\tr~aHiymi\tADJ\tSTEM").unwrap();
+        quran.attach_morphology(morphology).unwrap();
+        assert!(quran.morphology().is_some());
+    }
+
+    #[test]
+    fn test_from_path_missing_file() {
+        let result = Quran::from_path("does/not/exist.json");
+        assert!(matches!(result, Err(QuranError::FileOpenError(_))));
+    }
 }
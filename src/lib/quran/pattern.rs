@@ -0,0 +1,138 @@
+use regex::Regex;
+
+use crate::{error::QuranError, normalizer::Normalizer};
+
+use super::{hit::Span, verse::Ayah};
+
+/// A regular expression or wildcard pattern for
+/// [`QuranSearch::search_pattern`](super::search::QuranSearch::search_pattern).
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    regex: Regex,
+    /// The wildcard pattern as given, matched against whole words.
+    wildcard: Option<String>,
+}
+
+impl Pattern {
+    /// A regular expression, matched anywhere in the text.
+    pub fn regex(pattern: &str) -> Result<Self, QuranError> {
+        let regex = Regex::new(pattern)
+            .map_err(|e| QuranError::InvalidQuery(format!("{}: {}", pattern, e)))?;
+        Ok(Self {
+            regex,
+            wildcard: None,
+        })
+    }
+
+    /// A wildcard pattern matched against whole words: `?` stands for one
+    /// letter and `*` for any number, so `مف?و?` finds words of the مفعول
+    /// pattern. Marks after a letter are skipped, so the pattern also works on
+    /// fully vowelled text. The letters are folded by the search's normalizer
+    /// like the text is, so `ٱلۡمَغۡض?ب` works as well as `المغض?ب`.
+    pub fn wildcard(pattern: &str) -> Result<Self, QuranError> {
+        if pattern.contains(char::is_whitespace) {
+            return Err(QuranError::InvalidQuery(format!(
+                "{}: wildcard patterns match single words",
+                pattern
+            )));
+        }
+        Ok(Self {
+            regex: wildcard_regex(pattern),
+            wildcard: Some(pattern.to_string()),
+        })
+    }
+
+    /// The pattern as it applies to text folded by `normalizer`.
+    pub(in crate::quran) fn normalized(&self, normalizer: &Normalizer) -> Self {
+        match &self.wildcard {
+            Some(wildcard) => Self {
+                regex: wildcard_regex(&normalizer.normalize(wildcard)),
+                wildcard: self.wildcard.clone(),
+            },
+            None => self.clone(),
+        }
+    }
+
+    /// Every match in `normalized`, which is `text` of `ayah` folded by
+    /// `normalizer`. The spans refer to `text`.
+    pub(in crate::quran) fn find<'a>(
+        &self,
+        ayah: &'a Ayah,
+        text: &'a str,
+        normalized: &str,
+        normalizer: &Normalizer,
+    ) -> Vec<PatternMatch<'a>> {
+        let found: Vec<(usize, &str)> = if self.wildcard.is_some() {
+            normalized
+                .split_whitespace()
+                .filter(|word| self.regex.is_match(word))
+                .map(|word| (word.as_ptr() as usize - normalized.as_ptr() as usize, word))
+                .collect()
+        } else {
+            self.regex
+                .find_iter(normalized)
+                .filter(|found| !found.is_empty())
+                .map(|found| (found.start(), found.as_str()))
+                .collect()
+        };
+
+        found
+            .into_iter()
+            .map(|(offset, matched)| {
+                let start = normalized[..offset].chars().count();
+                let bytes = normalizer.source_bytes(text, start..start + matched.chars().count());
+                let before = &text[..bytes.start];
+                let chars = before.chars().count();
+                PatternMatch {
+                    ayah,
+                    text,
+                    matched: matched.to_string(),
+                    span: Span {
+                        chars: chars..chars + text[bytes.clone()].chars().count(),
+                        bytes,
+                    },
+                    word: before.split_whitespace().count()
+                        - usize::from(!before.is_empty() && !before.ends_with(char::is_whitespace)),
+                }
+            })
+            .collect()
+    }
+}
+
+/// The expression for a wildcard pattern without whitespace.
+fn wildcard_regex(pattern: &str) -> Regex {
+    let mut expression = String::from("^");
+    for c in pattern.chars() {
+        match c {
+            '?' => expression.push_str(r"\p{L}\p{M}*"),
+            '*' => expression.push_str(r"[\p{L}\p{M}]*"),
+            c => {
+                expression.push_str(&regex::escape(&c.to_string()));
+                expression.push_str(r"\p{M}*");
+            }
+        }
+    }
+    expression.push('$');
+    Regex::new(&expression).expect("escaped wildcard is a valid regex")
+}
+
+/// One match of a [`Pattern`].
+#[derive(Debug, Clone)]
+pub struct PatternMatch<'a> {
+    pub ayah: &'a Ayah,
+    /// The ayah in the searched layer, diacritics included.
+    pub text: &'a str,
+    /// The matched text after normalization.
+    pub matched: String,
+    /// Where the match lies in `text`, with the marks of its last letter.
+    pub span: Span,
+    /// Zero-based position of the word the match starts in.
+    pub word: usize,
+}
+
+impl<'a> PatternMatch<'a> {
+    /// The match as written in `text`.
+    pub fn as_written(&self) -> &'a str {
+        &self.text[self.span.bytes.clone()]
+    }
+}
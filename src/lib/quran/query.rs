@@ -0,0 +1,436 @@
+//! A small boolean query language for [`QuranSearch`](super::search::QuranSearch).
+//!
+//! ```text
+//! الله AND (رحمن OR رحيم) NOT "يوم القيامة"
+//! ```
+//!
+//! Words are matched under the search's [`MatchMode`]. Double quotes group
+//! several words into a phrase, parentheses group subqueries, and `AND`, `OR`
+//! and `NOT` combine them. Adjacent subqueries without an operator are joined
+//! with `AND`, and `A NOT B` reads as `A AND NOT B`.
+//!
+//! Proximity operators join two words or phrases:
+//!
+//! - `A NEAR/5 B`: at most 5 words apart, in either order, also across the
+//!   end of an ayah. Adjacent words are 1 apart, so `NEAR/1` asks for `A`
+//!   right next to `B`. `NEAR` alone means `NEAR/5`.
+//! - `A AYAH/1 B`: in the same ayah or in neighbouring ones. `AYAH` alone
+//!   means the same ayah.
+//! - `A NEXT/1 B`: `B` in the ayah right after the one with `A`. `NEXT` alone
+//!   means `NEXT/1`.
+//!
+//! None of them reach into another surah. Distances run from 1 to
+//! [`MAX_DISTANCE`], except that `AYAH/0` is allowed; `NEAR/0` and `NEXT/0`
+//! could never match. Proximity binds tightest, then `NOT`, then `AND`, then
+//! `OR`.
+
+use std::{collections::BTreeSet, fmt, str::FromStr};
+
+use crate::{error::QuranError, normalizer::Normalizer};
+
+use super::{
+    index::{InvertedIndex, Matches, Proximity},
+    matching::MatchMode,
+};
+
+/// The largest distance a proximity operator accepts. It is well beyond the
+/// longest surah in ayahs and covers most of it in words.
+pub const MAX_DISTANCE: u32 = 1000;
+
+/// A parsed query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    /// One word.
+    Term(String),
+    /// Several words in a row.
+    Phrase(String),
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+    Not(Box<Query>),
+    /// Two words or phrases close to each other.
+    Near {
+        left: String,
+        right: String,
+        proximity: Proximity,
+    },
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Self, QuranError> {
+        let tokens = lex(input)?;
+        let mut parser = Parser { tokens, next: 0 };
+        let query = parser.or()?;
+        match parser.peek() {
+            None => Ok(query),
+            Some(Token::RightParen) => Err(invalid("unmatched `)`")),
+            Some(token) => Err(invalid(&format!("unexpected {}", token))),
+        }
+    }
+
+    /// The ayahs matching the query, as positions in the whole text.
+    pub(in crate::quran) fn evaluate(
+        &self,
+        index: &InvertedIndex,
+        match_mode: MatchMode,
+        normalizer: &Normalizer,
+    ) -> BTreeSet<u32> {
+        match self {
+            Query::Term(text) | Query::Phrase(text) => index
+                .search(text, match_mode, normalizer)
+                .into_iter()
+                .collect(),
+            Query::And(left, right) => {
+                let left = left.evaluate(index, match_mode, normalizer);
+                let right = right.evaluate(index, match_mode, normalizer);
+                left.intersection(&right).copied().collect()
+            }
+            Query::Or(left, right) => {
+                let mut left = left.evaluate(index, match_mode, normalizer);
+                left.extend(right.evaluate(index, match_mode, normalizer));
+                left
+            }
+            Query::Near {
+                left,
+                right,
+                proximity,
+            } => index
+                .near(left, right, *proximity, match_mode, normalizer)
+                .into_iter()
+                .collect(),
+            Query::Not(query) => {
+                let excluded = query.evaluate(index, match_mode, normalizer);
+                (0..index.ayah_count() as u32)
+                    .filter(|ayah| !excluded.contains(ayah))
+                    .collect()
+            }
+        }
+    }
+
+    /// The words matched by the terms, phrases and proximity operators of the
+    /// query outside `NOT`, per ayah.
+    pub(in crate::quran) fn matched_words(
+        &self,
+        index: &InvertedIndex,
+        match_mode: MatchMode,
+        normalizer: &Normalizer,
+    ) -> Matches {
+        match self {
+            Query::Term(text) | Query::Phrase(text) => index.matches(text, match_mode, normalizer),
+            Query::Near {
+                left,
+                right,
+                proximity,
+            } => index.near_matches(left, right, *proximity, match_mode, normalizer),
+            Query::And(left, right) | Query::Or(left, right) => {
+                let mut matches = left.matched_words(index, match_mode, normalizer);
+                for (ayah, words) in right.matched_words(index, match_mode, normalizer) {
+                    matches.entry(ayah).or_default().extend(words);
+                }
+                matches
+            }
+            Query::Not(_) => Matches::new(),
+        }
+    }
+}
+
+impl FromStr for Query {
+    type Err = QuranError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    And,
+    Or,
+    Not,
+    Near(Proximity),
+    LeftParen,
+    RightParen,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "`{}`", word),
+            Token::Quoted(phrase) => write!(f, "\"{}\"", phrase),
+            Token::And => write!(f, "`AND`"),
+            Token::Or => write!(f, "`OR`"),
+            Token::Not => write!(f, "`NOT`"),
+            Token::Near(Proximity::Words(n)) => write!(f, "`NEAR/{}`", n),
+            Token::Near(Proximity::Ayahs(n)) => write!(f, "`AYAH/{}`", n),
+            Token::Near(Proximity::FollowingAyahs(n)) => write!(f, "`NEXT/{}`", n),
+            Token::LeftParen => write!(f, "`(`"),
+            Token::RightParen => write!(f, "`)`"),
+        }
+    }
+}
+
+fn lex(input: &str) -> Result<Vec<Token>, QuranError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LeftParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RightParen);
+            }
+            '"' => {
+                chars.next();
+                let mut phrase = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => phrase.push(c),
+                        None => return Err(invalid("unterminated phrase")),
+                    }
+                }
+                if phrase.trim().is_empty() {
+                    return Err(invalid("empty phrase"));
+                }
+                tokens.push(Token::Quoted(phrase.trim().to_string()));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || matches!(c, '(' | ')' | '"') {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push(match word.as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    _ => match proximity(&word)? {
+                        Some(proximity) => Token::Near(proximity),
+                        None => Token::Word(word),
+                    },
+                });
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Reads `NEAR/n`, `AYAH/n` and `NEXT/n`, with or without the distance.
+fn proximity(word: &str) -> Result<Option<Proximity>, QuranError> {
+    let (operator, distance) = match word.split_once('/') {
+        Some((operator, distance)) => (operator, Some(distance)),
+        None => (word, None),
+    };
+    let distance = |default: u32| match distance {
+        Some(distance) => match distance.parse::<u32>() {
+            Ok(n) if n > MAX_DISTANCE => Err(invalid(&format!(
+                "distance in `{}` is above {}",
+                word, MAX_DISTANCE
+            ))),
+            Ok(n) => Ok(n),
+            Err(_) => Err(invalid(&format!("invalid distance in `{}`", word))),
+        },
+        None => Ok(default),
+    };
+
+    Ok(Some(match operator {
+        "NEAR" => match distance(5)? {
+            0 => return Err(invalid("`NEAR/0` never matches; use `NEAR/1`")),
+            n => Proximity::Words(n),
+        },
+        "AYAH" => Proximity::Ayahs(distance(0)?),
+        "NEXT" => match distance(1)? {
+            0 => return Err(invalid("`NEXT/0` never matches; use `AYAH/0`")),
+            n => Proximity::FollowingAyahs(n),
+        },
+        _ => return Ok(None),
+    }))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    next: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.next).cloned();
+        self.next += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<Query, QuranError> {
+        let mut query = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.advance();
+            query = Query::Or(Box::new(query), Box::new(self.and()?));
+        }
+        Ok(query)
+    }
+
+    fn and(&mut self) -> Result<Query, QuranError> {
+        let mut query = self.unary()?;
+        loop {
+            match self.peek() {
+                Some(Token::And) => {
+                    self.advance();
+                    query = Query::And(Box::new(query), Box::new(self.unary()?));
+                }
+                Some(Token::Not | Token::Word(_) | Token::Quoted(_) | Token::LeftParen) => {
+                    query = Query::And(Box::new(query), Box::new(self.unary()?));
+                }
+                _ => return Ok(query),
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Result<Query, QuranError> {
+        if self.peek() == Some(&Token::Not) {
+            self.advance();
+            return Ok(Query::Not(Box::new(self.unary()?)));
+        }
+        self.near()
+    }
+
+    fn near(&mut self) -> Result<Query, QuranError> {
+        let query = self.primary()?;
+        let Some(&Token::Near(proximity)) = self.peek() else {
+            return Ok(query);
+        };
+        self.advance();
+        let right = self.primary()?;
+
+        match (query, right) {
+            (
+                Query::Term(left) | Query::Phrase(left),
+                Query::Term(right) | Query::Phrase(right),
+            ) => Ok(Query::Near {
+                left,
+                right,
+                proximity,
+            }),
+            _ => Err(invalid("proximity operators join words or phrases only")),
+        }
+    }
+
+    fn primary(&mut self) -> Result<Query, QuranError> {
+        match self.advance() {
+            Some(Token::Word(word)) => Ok(Query::Term(word)),
+            Some(Token::Quoted(phrase)) => Ok(Query::Phrase(phrase)),
+            Some(Token::LeftParen) => {
+                let query = self.or()?;
+                match self.advance() {
+                    Some(Token::RightParen) => Ok(query),
+                    _ => Err(invalid("missing `)`")),
+                }
+            }
+            Some(token) => Err(invalid(&format!("unexpected {}", token))),
+            None => Err(invalid("unexpected end of query")),
+        }
+    }
+}
+
+fn invalid(message: &str) -> QuranError {
+    QuranError::InvalidQuery(message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(word: &str) -> Box<Query> {
+        Box::new(Query::Term(word.to_string()))
+    }
+
+    #[test]
+    fn test_parse() {
+        let query = Query::parse("الله AND (رحمن OR رحيم) NOT \"يوم القيامة\"").unwrap();
+        assert_eq!(
+            query,
+            Query::And(
+                Box::new(Query::And(
+                    term("الله"),
+                    Box::new(Query::Or(term("رحمن"), term("رحيم")))
+                )),
+                Box::new(Query::Not(Box::new(Query::Phrase(
+                    "يوم القيامة".to_string()
+                ))))
+            )
+        );
+
+        assert_eq!(
+            Query::parse("a b OR c").unwrap(),
+            Query::Or(Box::new(Query::And(term("a"), term("b"))), term("c"))
+        );
+    }
+
+    #[test]
+    fn test_parse_near() {
+        assert_eq!(
+            Query::parse("الله NEAR/3 \"الرحمن الرحيم\" OR رب NEXT رحمن").unwrap(),
+            Query::Or(
+                Box::new(Query::Near {
+                    left: "الله".to_string(),
+                    right: "الرحمن الرحيم".to_string(),
+                    proximity: Proximity::Words(3),
+                }),
+                Box::new(Query::Near {
+                    left: "رب".to_string(),
+                    right: "رحمن".to_string(),
+                    proximity: Proximity::FollowingAyahs(1),
+                })
+            )
+        );
+        assert!(matches!(
+            Query::parse("الله AYAH رب").unwrap(),
+            Query::Near {
+                proximity: Proximity::Ayahs(0),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for input in [
+            "",
+            "(الله",
+            "الله)",
+            "\"يوم",
+            "AND الله",
+            "الله OR",
+            "\"\"",
+            "الله NEAR/x رب",
+            "الله NEAR/-1 رب",
+            "الله NEAR/0 رب",
+            "الله NEAR/1001 رب",
+            "الله AYAH/4294967295 رب",
+            "الله NEXT/0 رب",
+        ] {
+            assert!(
+                matches!(Query::parse(input), Err(QuranError::InvalidQuery(_))),
+                "{}",
+                input
+            );
+        }
+        for input in ["الله NEAR/1000 رب", "الله AYAH/0 رب"] {
+            assert!(Query::parse(input).is_ok(), "{}", input);
+        }
+    }
+}
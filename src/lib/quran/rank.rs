@@ -0,0 +1,127 @@
+//! BM25 relevance ranking.
+
+use std::collections::HashMap;
+
+use super::{division::Division, search::QuranSearch, surah::Surah, verse::Ayah};
+
+/// The stretch of text that is scored and returned as one result.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RankUnit {
+    #[default]
+    Ayah,
+    Surah,
+    /// A ruku', page or other division, e.g.
+    /// `RankUnit::Division(Division::Page)`.
+    Division(Division),
+}
+
+/// BM25 parameters. The defaults are the usual k1 = 1.2 and b = 0.75.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bm25 {
+    /// How quickly repeated occurrences of a term stop adding to the score.
+    pub k1: f32,
+    /// How strongly long units are penalized, from 0 (not at all) to 1.
+    pub b: f32,
+}
+
+impl Default for Bm25 {
+    fn default() -> Self {
+        Self { k1: 1.2, b: 0.75 }
+    }
+}
+
+/// One scored unit.
+#[derive(Debug, Clone)]
+pub struct RankedHit<'a> {
+    pub score: f32,
+    /// The surah of the first matching ayah; a division may span several.
+    pub surah: &'a Surah,
+    /// The ayahs of the unit containing at least one query term, in order.
+    pub ayahs: Vec<&'a Ayah>,
+}
+
+impl Bm25 {
+    /// Scores every unit containing at least one word of `query`, best first.
+    /// Ties keep text order. Only the ayahs in the scope of `search` are
+    /// counted, so term rarity is measured within it.
+    pub(in crate::quran) fn rank<'a>(
+        &self,
+        search: &QuranSearch<'a>,
+        query: &str,
+        unit: RankUnit,
+    ) -> Vec<RankedHit<'a>> {
+        let quran = search.quran;
+        let index = quran.index(&search.layer, &search.normalizer);
+        let (match_mode, normalizer) = (search.match_mode, &search.normalizer);
+        let ayah_at = |ayah: u32| {
+            let (surah, ayah) = index.location(ayah);
+            &quran.surahs()[surah].ayahs()[ayah]
+        };
+        let in_scope = |ayah: u32| search.in_scope(ayah_at(ayah));
+        let unit_of = |ayah: u32| match unit {
+            RankUnit::Ayah => ayah as usize,
+            RankUnit::Surah => index.location(ayah).0,
+            RankUnit::Division(division) => division.of(ayah_at(ayah).verse_ref()) as usize,
+        };
+
+        let mut unit_lengths: HashMap<usize, f32> = HashMap::new();
+        for ayah in (0..index.ayah_count() as u32).filter(|&ayah| in_scope(ayah)) {
+            *unit_lengths.entry(unit_of(ayah)).or_default() +=
+                index.ayah_len(ayah, match_mode) as f32;
+        }
+        let units = unit_lengths.len() as f32;
+        let average_length = unit_lengths.values().sum::<f32>() / units.max(1.0);
+
+        let mut scores: HashMap<usize, f32> = HashMap::new();
+        let mut matched: HashMap<usize, Vec<u32>> = HashMap::new();
+
+        let mut terms = index.terms(query, match_mode, normalizer);
+        terms.sort();
+        terms.dedup();
+        for term in &terms {
+            let mut frequencies: HashMap<usize, f32> = HashMap::new();
+            for posting in index
+                .matching_postings(term, match_mode)
+                .into_iter()
+                .filter(|posting| in_scope(posting.ayah))
+            {
+                let unit = unit_of(posting.ayah);
+                *frequencies.entry(unit).or_default() += 1.0;
+                matched.entry(unit).or_default().push(posting.ayah);
+            }
+
+            let documents = frequencies.len() as f32;
+            let idf = (1.0 + (units - documents + 0.5) / (documents + 0.5)).ln();
+            for (unit, frequency) in frequencies {
+                let length = unit_lengths[&unit];
+                let norm = self.k1 * (1.0 - self.b + self.b * length / average_length);
+                *scores.entry(unit).or_default() +=
+                    idf * frequency * (self.k1 + 1.0) / (frequency + norm);
+            }
+        }
+
+        let mut hits: Vec<(usize, RankedHit<'a>)> = scores
+            .into_iter()
+            .map(|(unit, score)| {
+                let mut ayahs = matched.remove(&unit).unwrap_or_default();
+                ayahs.sort_unstable();
+                ayahs.dedup();
+                let surah = &quran.surahs()[index.location(ayahs[0]).0];
+                let ayahs = ayahs.into_iter().map(ayah_at).collect();
+                (
+                    unit,
+                    RankedHit {
+                        score,
+                        surah,
+                        ayahs,
+                    },
+                )
+            })
+            .collect();
+
+        hits.sort_by(|(a_unit, a), (b_unit, b)| {
+            b.score.total_cmp(&a.score).then(a_unit.cmp(b_unit))
+        });
+        hits.into_iter().map(|(_, hit)| hit).collect()
+    }
+}
@@ -0,0 +1,380 @@
+//! Verse references such as `2:255`, `البقرة ٢٥٥`, `Al-Baqarah 255` and
+//! `3:190-194, 4:1`.
+//!
+//! A surah is given by number, by its Arabic name or by its transliteration,
+//! and numbers may be written with Arabic-Indic digits. Parsing checks
+//! references against the canonical verse counts in [`meta`](super::meta);
+//! resolving checks them against a loaded [`Quran`].
+
+use std::{collections::HashMap, fmt, str::FromStr, sync::LazyLock};
+
+use crate::{error::QuranError, normalizer::Normalizer};
+
+use super::{
+    meta::{surah_info, SURAHS},
+    verse::Ayah,
+    Quran,
+};
+
+static SURAH_NAMES: LazyLock<HashMap<String, u32>> = LazyLock::new(|| {
+    let mut names = HashMap::new();
+    for info in &SURAHS {
+        names.insert(arabic_key(info.name), info.id);
+        names.insert(latin_key(info.transliteration), info.id);
+        // "Baqarah" as well as "Al-Baqarah".
+        if let Some((article, name)) = info.transliteration.split_once('-') {
+            if article.starts_with('A') && article.len() <= 3 {
+                names.entry(latin_key(name)).or_insert(info.id);
+            }
+        }
+    }
+    names
+});
+
+static NAME_NORMALIZER: LazyLock<Normalizer> = LazyLock::new(Normalizer::full);
+
+fn arabic_key(name: &str) -> String {
+    let name = NAME_NORMALIZER.normalize(name);
+    let name = name.trim();
+    let name = name
+        .strip_prefix("سوره ")
+        .or_else(|| name.strip_prefix("سورة "))
+        .unwrap_or(name);
+    name.split_whitespace().collect()
+}
+
+fn latin_key(name: &str) -> String {
+    let name = name.to_lowercase();
+    let name = name.trim();
+    let name = name.strip_prefix("surah ").unwrap_or(name);
+    name.chars().filter(char::is_ascii_alphanumeric).collect()
+}
+
+/// One ayah, by surah and ayah number, both counting from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VerseRef {
+    pub surah: u32,
+    pub ayah: u32,
+}
+
+impl VerseRef {
+    pub fn new(surah: u32, ayah: u32) -> Self {
+        Self { surah, ayah }
+    }
+
+    /// Parses a single reference such as `2:255` or `البقرة ٢٥٥`.
+    pub fn parse(input: &str) -> Result<Self, QuranError> {
+        let range = VerseRange::parse(input)?;
+        if range.start != range.end {
+            return Err(invalid(input, "expected a single ayah"));
+        }
+        Ok(range.start)
+    }
+
+    /// The ayah in `quran`.
+    pub fn resolve<'a>(&self, quran: &'a Quran) -> Result<&'a Ayah, QuranError> {
+        quran.get_ayah(self.surah as usize, self.ayah as usize)
+    }
+
+    /// Fails unless the ayah exists in the canonical numbering.
+    fn check(&self) -> Result<(), QuranError> {
+        let info = surah_info(self.surah).ok_or(QuranError::SurahOutOfRange {
+            surah: self.surah,
+            surahs: SURAHS.len(),
+        })?;
+        if self.ayah == 0 || self.ayah > info.total_verses {
+            return Err(QuranError::AyahOutOfRange {
+                surah: self.surah,
+                ayah: self.ayah,
+                ayahs: info.total_verses as usize,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for VerseRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.surah, self.ayah)
+    }
+}
+
+impl FromStr for VerseRef {
+    type Err = QuranError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// The ayahs from `start` to `end`, both included, possibly across surahs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VerseRange {
+    pub start: VerseRef,
+    pub end: VerseRef,
+}
+
+impl VerseRange {
+    /// Fails with [`QuranError::InvalidReference`] when `end` comes before
+    /// `start`.
+    pub fn new(start: VerseRef, end: VerseRef) -> Result<Self, QuranError> {
+        if end < start {
+            return Err(QuranError::InvalidReference(format!(
+                "{}-{}: the range ends before it starts",
+                start, end
+            )));
+        }
+        Ok(Self { start, end })
+    }
+
+    pub fn single(verse: VerseRef) -> Self {
+        Self {
+            start: verse,
+            end: verse,
+        }
+    }
+
+    /// Every ayah of surah `surah` in the canonical numbering.
+    pub fn surah(surah: u32) -> Result<Self, QuranError> {
+        let info = surah_info(surah).ok_or(QuranError::SurahOutOfRange {
+            surah,
+            surahs: SURAHS.len(),
+        })?;
+        Ok(Self {
+            start: VerseRef::new(surah, 1),
+            end: VerseRef::new(surah, info.total_verses),
+        })
+    }
+
+    /// Parses one reference: `2:255`, `3:190-194`, `2:10-3:20`, a whole
+    /// surah such as `36` or `يس`, or a surah name followed by an ayah or
+    /// range, as in `البقرة 255` or `Al-Baqarah:255`.
+    pub fn parse(input: &str) -> Result<Self, QuranError> {
+        let text: String = input
+            .trim()
+            .chars()
+            .map(|c| match c {
+                '\u{0660}'..='\u{0669}' => char::from(b'0' + (c as u32 - 0x0660) as u8),
+                '\u{06F0}'..='\u{06F9}' => char::from(b'0' + (c as u32 - 0x06F0) as u8),
+                '\u{2013}' | '\u{2014}' => '-',
+                c => c,
+            })
+            .collect();
+        if text.is_empty() {
+            return Err(invalid(input, "empty reference"));
+        }
+        // `البقرة 255 - 260` must not read `255 -` as part of the name.
+        let text = text.split('-').map(str::trim).collect::<Vec<_>>().join("-");
+
+        let (surah, ayahs) = match text.split_once(':') {
+            Some((surah, ayahs)) => (surah.trim(), Some(ayahs.trim())),
+            None => match text.rsplit_once(char::is_whitespace) {
+                Some((surah, ayahs)) if ayahs.starts_with(|c: char| c.is_ascii_digit()) => {
+                    (surah.trim(), Some(ayahs))
+                }
+                _ => (text.as_str(), None),
+            },
+        };
+        let surah = parse_surah(surah).ok_or_else(|| invalid(input, "unknown surah"))?;
+
+        let Some(ayahs) = ayahs else {
+            return Self::surah(surah);
+        };
+        let (first, last) = match ayahs.split_once('-') {
+            Some((first, last)) => (first.trim(), Some(last.trim())),
+            None => (ayahs, None),
+        };
+        let start = VerseRef::new(surah, parse_number(input, first)?);
+        let end = match last {
+            None => start,
+            Some(last) => match last.split_once(':') {
+                Some((surah, ayah)) => VerseRef::new(
+                    parse_surah(surah.trim()).ok_or_else(|| invalid(input, "unknown surah"))?,
+                    parse_number(input, ayah.trim())?,
+                ),
+                None => VerseRef::new(surah, parse_number(input, last)?),
+            },
+        };
+
+        start.check()?;
+        end.check()?;
+        Self::new(start, end)
+    }
+
+    /// Parses a list of references separated by commas or semicolons, such
+    /// as `3:190-194, 4:1`.
+    pub fn parse_list(input: &str) -> Result<Vec<Self>, QuranError> {
+        input.split([',', ';', '،', '؛']).map(Self::parse).collect()
+    }
+
+    pub fn contains(&self, verse: &VerseRef) -> bool {
+        (self.start..=self.end).contains(verse)
+    }
+
+    /// The ayahs of the range in `quran`, in order. Fails when the range is
+    /// reversed, which the public fields allow.
+    pub fn resolve<'a>(&self, quran: &'a Quran) -> Result<Vec<&'a Ayah>, QuranError> {
+        let range = Self::new(self.start, self.end)?;
+        range.start.resolve(quran)?;
+        range.end.resolve(quran)?;
+
+        Ok(
+            quran.surahs()[range.start.surah as usize - 1..range.end.surah as usize]
+                .iter()
+                .flat_map(|surah| surah.ayahs().iter())
+                .filter(|ayah| self.contains(&VerseRef::new(ayah.surah_id(), ayah.number() as u32)))
+                .collect(),
+        )
+    }
+}
+
+impl fmt::Display for VerseRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start)
+        } else if self.start.surah == self.end.surah {
+            write!(f, "{}-{}", self.start, self.end.ayah)
+        } else {
+            write!(f, "{}-{}", self.start, self.end)
+        }
+    }
+}
+
+impl FromStr for VerseRange {
+    type Err = QuranError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// A surah number or name.
+fn parse_surah(surah: &str) -> Option<u32> {
+    if surah.chars().all(|c| c.is_ascii_digit()) {
+        return surah.parse().ok();
+    }
+    SURAH_NAMES
+        .get(&arabic_key(surah))
+        .or_else(|| SURAH_NAMES.get(&latin_key(surah)))
+        .copied()
+}
+
+fn parse_number(input: &str, number: &str) -> Result<u32, QuranError> {
+    number
+        .parse()
+        .map_err(|_| invalid(input, &format!("`{}` is not an ayah number", number)))
+}
+
+fn invalid(input: &str, message: &str) -> QuranError {
+    QuranError::InvalidReference(format!("{}: {}", input.trim(), message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: (u32, u32), end: (u32, u32)) -> VerseRange {
+        VerseRange::new(VerseRef::new(start.0, start.1), VerseRef::new(end.0, end.1)).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(VerseRef::parse("2:255").unwrap(), VerseRef::new(2, 255));
+        assert_eq!(
+            VerseRef::parse("البقرة ٢٥٥").unwrap(),
+            VerseRef::new(2, 255)
+        );
+        assert_eq!(
+            VerseRef::parse("Al-Baqarah:255").unwrap(),
+            VerseRef::new(2, 255)
+        );
+        assert_eq!(
+            VerseRef::parse("surah baqarah 255").unwrap(),
+            VerseRef::new(2, 255)
+        );
+        assert_eq!(
+            VerseRange::parse("آل عمران 190-194").unwrap(),
+            range((3, 190), (3, 194))
+        );
+        assert_eq!(
+            VerseRange::parse("2:10 - 3:20").unwrap(),
+            range((2, 10), (3, 20))
+        );
+        for input in [
+            "البقرة 255 - 260",
+            "البقرة 255-260",
+            "2:255 - 260",
+            "Al-Baqarah 255 – 260",
+        ] {
+            assert_eq!(
+                VerseRange::parse(input).unwrap(),
+                range((2, 255), (2, 260)),
+                "{}",
+                input
+            );
+        }
+        assert_eq!(VerseRange::parse("يس").unwrap(), range((36, 1), (36, 83)));
+        assert_eq!(
+            VerseRange::parse_list("3:190-194, 4:1").unwrap(),
+            vec![range((3, 190), (3, 194)), range((4, 1), (4, 1))]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            VerseRef::parse("115:1"),
+            Err(QuranError::SurahOutOfRange { surah: 115, .. })
+        ));
+        assert!(matches!(
+            VerseRef::parse("1:8"),
+            Err(QuranError::AyahOutOfRange { ayahs: 7, .. })
+        ));
+        assert!(matches!(
+            VerseRef::parse("1:0"),
+            Err(QuranError::AyahOutOfRange { .. })
+        ));
+        for input in ["", "Baqara 2", "2:x", "2:5-3", "3:20-2:10", "2:1-"] {
+            assert!(
+                matches!(VerseRef::parse(input), Err(QuranError::InvalidReference(_))),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        for input in ["2:255", "3:190-194", "2:10-3:20"] {
+            assert_eq!(VerseRange::parse(input).unwrap().to_string(), input);
+        }
+    }
+
+    #[test]
+    fn test_resolve() {
+        let quran = Quran::new().unwrap();
+
+        let ayah = VerseRef::new(2, 255).resolve(&quran).unwrap();
+        assert_eq!((ayah.surah_id(), ayah.number()), (2, 255));
+
+        let ayahs = range((1, 6), (2, 2)).resolve(&quran).unwrap();
+        let refs: Vec<(u32, usize)> = ayahs
+            .iter()
+            .map(|ayah| (ayah.surah_id(), ayah.number()))
+            .collect();
+        assert_eq!(refs, vec![(1, 6), (1, 7), (2, 1), (2, 2)]);
+
+        assert!(matches!(
+            VerseRef::new(0, 1).resolve(&quran),
+            Err(QuranError::SurahOutOfRange { surahs: 114, .. })
+        ));
+        let reversed = VerseRange {
+            start: VerseRef::new(3, 1),
+            end: VerseRef::new(1, 1),
+        };
+        assert!(matches!(
+            reversed.resolve(&quran),
+            Err(QuranError::InvalidReference(_))
+        ));
+    }
+}
@@ -1,105 +1,356 @@
-use super::Quran;
+use crate::{error::QuranError, morphology::MorphologyQuery, normalizer::Normalizer};
+
+use super::{
+    division::Scope,
+    fuzzy::{self, FuzzyMatch},
+    hit::SearchHit,
+    index::{InvertedIndex, Matches, Proximity},
+    layer::TextLayer,
+    matching::MatchMode,
+    pattern::{Pattern, PatternMatch},
+    query::Query,
+    rank::{Bm25, RankUnit, RankedHit},
+    reference::{VerseRange, VerseRef},
+    verse::Ayah,
+    Quran,
+};
 
 pub struct QuranSearch<'a> {
-    quran: &'a Quran,
+    pub(in crate::quran) quran: &'a Quran,
+    pub(in crate::quran) layer: TextLayer,
+    pub(in crate::quran) normalizer: Normalizer,
+    pub(in crate::quran) match_mode: MatchMode,
+    scope: Option<VerseRange>,
 }
 
 impl<'a> QuranSearch<'a> {
     pub fn new(quran: &'a Quran) -> QuranSearch<'a> {
-        QuranSearch { quran }
+        QuranSearch {
+            quran,
+            layer: quran.primary_layer().clone(),
+            normalizer: Normalizer::new(),
+            match_mode: MatchMode::default(),
+            scope: None,
+        }
+    }
+
+    /// Matches against `layer` instead of the primary text. Results carry the
+    /// ayah text in that layer.
+    pub fn with_layer(mut self, layer: TextLayer) -> Result<Self, QuranError> {
+        self.quran.check_layer(&layer)?;
+        self.layer = layer;
+        Ok(self)
+    }
+
+    /// Folds the text and the search terms with `normalizer` before matching,
+    /// e.g. [`Normalizer::full`] to find "الاخرة" in "وَبِٱلۡأٓخِرَةِ".
+    pub fn with_normalizer(mut self, normalizer: Normalizer) -> Self {
+        self.normalizer = normalizer;
+        self
+    }
+
+    /// Compares terms with words under `match_mode`; whole words by default.
+    pub fn with_match_mode(mut self, match_mode: MatchMode) -> Self {
+        self.match_mode = match_mode;
+        self
+    }
+
+    /// Searches only the ayahs of `scope`, such as a juz' or a page. Fails
+    /// when the scope names a division that does not exist or ayahs that are
+    /// not in the text.
+    pub fn with_scope(mut self, scope: Scope) -> Result<Self, QuranError> {
+        self.scope = Some(scope.range_in(self.quran)?);
+        Ok(self)
+    }
+
+    pub(in crate::quran) fn in_scope(&self, ayah: &Ayah) -> bool {
+        self.scope
+            .is_none_or(|scope| scope.contains(&ayah.verse_ref()))
+    }
+
+    /// Looks `search_term` up in the shared index.
+    fn matching(&self, search_term: &str, match_mode: MatchMode) -> Vec<SearchHit<'a>> {
+        let index = self.quran.index(&self.layer, &self.normalizer);
+        let matches = index.matches(search_term, match_mode, &self.normalizer);
+        self.hits(&index, matches, match_mode)
+    }
+
+    fn evaluate(&self, query: &Query) -> Vec<SearchHit<'a>> {
+        let index = self.quran.index(&self.layer, &self.normalizer);
+        let mut words = query.matched_words(&index, self.match_mode, &self.normalizer);
+        let matches = query
+            .evaluate(&index, self.match_mode, &self.normalizer)
+            .into_iter()
+            .map(|ayah| (ayah, words.remove(&ayah).unwrap_or_default()))
+            .collect();
+        self.hits(&index, matches, self.match_mode)
+    }
+
+    /// Turns the word positions the index reports into hits on the text.
+    fn hits(
+        &self,
+        index: &InvertedIndex,
+        matches: Matches,
+        match_mode: MatchMode,
+    ) -> Vec<SearchHit<'a>> {
+        let quran = self.quran;
+        matches
+            .into_iter()
+            .filter_map(|(position, words)| {
+                let (surah, ayah) = index.location(position);
+                let ayah = &quran.surahs()[surah].ayahs()[ayah];
+                if !self.in_scope(ayah) {
+                    return None;
+                }
+                let text = ayah.text_in(&self.layer).unwrap_or_default();
+                let indexed = index.indexed_words(text, match_mode, &self.normalizer);
+                let words = words
+                    .into_iter()
+                    .filter_map(|word| indexed.get(word as usize).copied());
+                Some(SearchHit::new(ayah, position as usize, text, words))
+            })
+            .collect()
+    }
+
+    pub fn search(&mut self, search_term: &str) -> Vec<SearchHit<'a>> {
+        self.matching(search_term, self.match_mode)
+    }
+
+    /// Like [`QuranSearch::search`], but returns the matching ayahs themselves so
+    /// callers can reach their translation and surah metadata.
+    pub fn search_ayahs(&self, search_term: &str) -> Vec<&'a Ayah> {
+        let quran = self.quran;
+        let index = quran.index(&self.layer, &self.normalizer);
+        index
+            .search(search_term, self.match_mode, &self.normalizer)
+            .into_iter()
+            .map(|position| {
+                let (surah, ayah) = index.location(position);
+                &quran.surahs()[surah].ayahs()[ayah]
+            })
+            .filter(|ayah| self.in_scope(ayah))
+            .collect()
     }
 
-    pub fn search(&mut self, search_term: &str) -> Vec<(String, usize, String)> {
-        let search_results = self
-            .quran
+    /// Searches on segmented stems, so that "الله" also finds "لِلَّهِ" and
+    /// "الأرض" finds "وَٱلۡأَرۡضِ", whatever the match mode.
+    pub fn search_stem(&mut self, search_term: &str) -> Vec<SearchHit<'a>> {
+        self.matching(search_term, MatchMode::Stem)
+    }
+
+    /// Finds every match of a regular expression or wildcard [`Pattern`].
+    ///
+    /// The pattern runs on the text of the search's layer after folding with
+    /// its normalizer, so by default without diacritics; use
+    /// [`Normalizer::none`] to match the raw text. The spans of the matches
+    /// refer to the text as written. Every ayah is scanned, but the folded
+    /// text is computed once and kept with the index.
+    pub fn search_pattern(&self, pattern: &Pattern) -> Vec<PatternMatch<'a>> {
+        let quran = self.quran;
+        let index = quran.index(&self.layer, &self.normalizer);
+        let pattern = pattern.normalized(&self.normalizer);
+        index
+            .normalized_texts(quran)
+            .iter()
+            .enumerate()
+            .flat_map(|(position, normalized)| {
+                let ayah = index.ayah(quran, position as u32);
+                match ayah.text_in(&self.layer) {
+                    Some(text) if self.in_scope(ayah) => {
+                        pattern.find(ayah, text, normalized, &self.normalizer)
+                    }
+                    _ => Vec::new(),
+                }
+            })
+            .collect()
+    }
+
+    /// Finds words spelled approximately like `search_term`, tolerating the
+    /// differences between modern and Uthmani orthography, so "الصلاة" finds
+    /// "ٱلصَّلَوٰةَ". Returns the closest match of each ayah within
+    /// `max_distance`, closest first; see [`fuzzy`] for the costs. Distances
+    /// are measured once per distinct word of the layer.
+    pub fn search_fuzzy(&self, search_term: &str, max_distance: f32) -> Vec<FuzzyMatch<'a>> {
+        let quran = self.quran;
+        let index = quran.index(&self.layer, &self.normalizer);
+        let ayah = |position| {
+            let ayah = index.ayah(quran, position);
+            if !self.in_scope(ayah) {
+                return None;
+            }
+            Some((ayah, ayah.text_in(&self.layer)?))
+        };
+        fuzzy::search(index.vocabulary(quran), ayah, search_term, max_distance)
+    }
+
+    /// Searches the attached morphology, e.g. for every imperfect verb of the
+    /// root ق-و-ل in Meccan surahs. The matching words are those of the
+    /// morphology, counted among the words of the layer that have letters.
+    pub fn search_morphology(
+        &mut self,
+        query: &MorphologyQuery,
+    ) -> Result<Vec<SearchHit<'a>>, QuranError> {
+        let quran = self.quran;
+        if quran.morphology().is_none() {
+            return Err(QuranError::MorphologyNotLoaded);
+        }
+
+        let search_results = quran
             .surahs()
             .iter()
-            .flat_map(|surah| {
-                surah
-                    .ayahs()
-                    .iter()
-                    .filter(|ayah| ayah.contains_word(search_term))
-                    .map(move |ayah| {
-                        (
-                            surah.name().to_string(),
-                            ayah.number(),
-                            ayah.text().to_string(),
-                        )
-                    })
+            .flat_map(|surah| surah.ayahs().iter().map(move |ayah| (surah, ayah)))
+            .enumerate()
+            .filter(|(_, (surah, ayah))| {
+                self.in_scope(ayah)
+                    && query
+                        .revelation_type_filter()
+                        .is_none_or(|revelation_type| {
+                            surah.revelation_type() == Some(revelation_type)
+                        })
             })
-            .collect::<Vec<_>>();
+            .filter_map(|(position, (_, ayah))| {
+                let matched: Vec<u32> = quran
+                    .ayah_morphology(ayah)
+                    .into_iter()
+                    .filter(|word| query.matches(word))
+                    .map(|word| word.location.word)
+                    .collect();
+                if matched.is_empty() {
+                    return None;
+                }
 
-        search_results
+                let text = ayah.text_in(&self.layer).unwrap_or_default();
+                let indexed =
+                    InvertedIndex::lettered_words(text, MatchMode::Stem, &self.normalizer);
+                let words = matched.into_iter().filter_map(|word| {
+                    (word as usize)
+                        .checked_sub(1)
+                        .and_then(|i| indexed.get(i))
+                        .copied()
+                });
+                Some(SearchHit::new(ayah, position, text, words))
+            })
+            .collect();
+
+        Ok(search_results)
+    }
+
+    /// Evaluates a boolean [`Query`] such as
+    /// `الله AND (رحمن OR رحيم) NOT "يوم القيامة"`. Each matching ayah is
+    /// returned once, in text order.
+    /// The matched words are those of the terms outside `NOT`.
+    pub fn search_query(&mut self, query: &str) -> Result<Vec<SearchHit<'a>>, QuranError> {
+        let query = Query::parse(query)?;
+        Ok(self.evaluate(&query))
     }
 
-    /// Searches for verses that contain multiple terms.
-    pub fn search_multiple_terms(
+    /// Finds ayahs where `left` and `right` occur within `proximity` of each
+    /// other, e.g. within 5 words or in consecutive ayahs of one surah.
+    pub fn search_near(
         &mut self,
-        search_terms: Vec<&str>,
-    ) -> Vec<(String, usize, String)> {
-        let mut search_results = vec![];
+        left: &str,
+        right: &str,
+        proximity: Proximity,
+    ) -> Vec<SearchHit<'a>> {
+        let query = Query::Near {
+            left: left.to_string(),
+            right: right.to_string(),
+            proximity,
+        };
+        self.evaluate(&query)
+    }
 
-        for search_term in search_terms {
-            let mut temp_results = self.search(search_term);
-            search_results.append(&mut temp_results);
-        }
+    /// Ranks ayahs or surahs by how well they match the words of `query`,
+    /// using BM25 with default parameters. Rare words weigh more than common
+    /// ones, and each result carries its score.
+    pub fn search_ranked(&self, query: &str, unit: RankUnit) -> Vec<RankedHit<'a>> {
+        self.search_ranked_with(query, unit, Bm25::default())
+    }
+
+    /// Like [`QuranSearch::search_ranked`], with custom BM25 parameters.
+    pub fn search_ranked_with(
+        &self,
+        query: &str,
+        unit: RankUnit,
+        bm25: Bm25,
+    ) -> Vec<RankedHit<'a>> {
+        bm25.rank(self, query, unit)
+    }
 
-        search_results
+    /// Searches for verses that contain any of the terms. Each matching ayah
+    /// is returned once, in text order.
+    pub fn search_multiple_terms(&mut self, search_terms: Vec<&str>) -> Vec<SearchHit<'a>> {
+        let Some(query) = search_terms
+            .into_iter()
+            .map(|term| Query::Phrase(term.to_string()))
+            .reduce(|left, right| Query::Or(Box::new(left), Box::new(right)))
+        else {
+            return Vec::new();
+        };
+
+        self.evaluate(&query)
     }
 
-    /// Searches for verses that are located within a certain range of ayahs.
+    /// Searches the ayahs from `start` to `end`, both included, which may lie
+    /// in different surahs, e.g. `2:10` to `3:20`. Fails when the range is
+    /// reversed or either end is not in the text.
     pub fn search_range(
         &mut self,
-        start_ayah_number: usize,
-        end_ayah_number: usize,
+        start: VerseRef,
+        end: VerseRef,
         search_term: &str,
-    ) -> Vec<(String, usize, String)> {
-        let mut search_results = vec![];
+    ) -> Result<Vec<SearchHit<'a>>, QuranError> {
+        let range = VerseRange::new(start, end)?;
+        start.resolve(self.quran)?;
+        end.resolve(self.quran)?;
 
-        for ayah in self
-            .quran
-            .ayas()
-            .iter()
-            .skip(start_ayah_number)
-            .take(end_ayah_number - start_ayah_number + 1)
-        {
-            if ayah.contains_word(search_term) {
-                search_results.push((
-                    ayah.surah_name().to_string(),
-                    ayah.number(),
-                    ayah.text().to_string(),
-                ));
-            }
-        }
+        Ok(self
+            .matching(search_term, self.match_mode)
+            .into_iter()
+            .filter(|hit| range.contains(&VerseRef::new(hit.surah_id, hit.ayah_number as u32)))
+            .collect())
+    }
 
-        search_results
+    /// Searches the ayahs at positions `start..=end` of the whole text,
+    /// counting from zero across surah boundaries, so that 7 is 2:1.
+    pub fn search_global_range(
+        &mut self,
+        start: usize,
+        end: usize,
+        search_term: &str,
+    ) -> Vec<SearchHit<'a>> {
+        self.matching(search_term, self.match_mode)
+            .into_iter()
+            .filter(|hit| (start..=end).contains(&hit.index))
+            .collect()
     }
 
-    /// Searches for verses that are located in a certain surah.
+    /// Searches for verses that are located in surah `surah_number`, counting
+    /// from 1. Fails with [`QuranError::SurahOutOfRange`] for a surah that
+    /// does not exist.
     pub fn search_surah(
         &mut self,
         search_term: &str,
         surah_number: usize,
-    ) -> Vec<(String, usize, String)> {
-        let mut search_results = vec![];
-
-        for ayah in self.quran.surahs()[surah_number - 1].ayahs().iter() {
-            if ayah.contains_word(search_term) {
-                search_results.push((
-                    ayah.surah_name().to_string(),
-                    ayah.number(),
-                    ayah.text().to_string(),
-                ));
-            }
-        }
-
-        search_results
+    ) -> Result<Vec<SearchHit<'a>>, QuranError> {
+        let surah_id = self.quran.get_surah(surah_number)?.id();
+        Ok(self
+            .matching(search_term, self.match_mode)
+            .into_iter()
+            .filter(|hit| hit.surah_id == surah_id)
+            .collect())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        morphology::{Aspect, Morphology},
+        quran::division::Division,
+        quran::hit::Highlighter,
+        quran::surah::RevelationType,
+    };
 
     // Helper function to create a test Quran instance
     fn create_test_quran() -> Quran {
@@ -121,6 +372,350 @@ mod tests {
         assert_eq!(search_results.len(), 2);
     }
 
+    #[test]
+    fn test_search_hit() {
+        let quran = create_test_quran();
+        let mut search = QuranSearch::new(&quran);
+
+        let hits = search.search_surah("بسم الله الرحمن", 1).unwrap();
+        assert_eq!(hits.len(), 1);
+        let hit = &hits[0];
+        assert_eq!((hit.surah_id, hit.ayah_number, hit.index), (1, 1, 0));
+        assert_eq!(hit.words, vec![0, 1, 2]);
+        assert_eq!(hit.spans[0].chars.start, 0);
+        let end = hit.spans[2].bytes.end;
+        assert_eq!(hit.spans[2].chars.end, hit.text[..end].chars().count());
+        assert_eq!(
+            hit.highlight(Highlighter::Markdown),
+            format!("**{}**{}", &hit.text[..end], &hit.text[end..])
+        );
+
+        // لِلَّهِ is the second word of 1:2.
+        let hits = search.search_stem("الله");
+        assert_eq!(hits[1].words, vec![1]);
+        assert_eq!(crate::remove_diacritics(hits[1].matched()[0]), "لله");
+
+        let hits = search.search_query("الحمد AND NOT الرحيم OR مالك").unwrap();
+        assert_eq!(hits[0].ayah_number, 2);
+        assert_eq!(hits[0].words, vec![0]);
+    }
+
+    #[test]
+    fn test_search_stem() {
+        let quran = create_test_quran();
+        let mut search = QuranSearch::new(&quran);
+
+        // "ٱللَّهِ" in the first ayah and "لِلَّهِ" in the second.
+        let search_results = search.search_surah("الله", 1).unwrap();
+        assert_eq!(search_results.len(), 1);
+        let search_results = search.search_stem("الله");
+        assert_eq!(search_results[0].ayah_number, 1);
+        assert_eq!(search_results[1].ayah_number, 2);
+    }
+
+    #[test]
+    fn test_search_morphology() {
+        let mut quran = create_test_quran();
+        let morphology = Morphology::parse(
+            "(2:8:3:1)\tyaqu\tV\tSTEM|POS:V|IMPF|LEM:qaAla|ROOT:qwl|3MS\n\
+             (2:11:2:1)\tqiyla\tV\tSTEM|POS:V|PERF|PASS|LEM:qaAla|ROOT:qwl|3MS\n\
+             (36:82:7:1)\tyaquwlu\tV\tSTEM|POS:V|IMPF|LEM:qaAla|ROOT:qwl|3MS\n",
+        )
+        .unwrap();
+
+        assert!(matches!(
+            QuranSearch::new(&quran).search_morphology(&MorphologyQuery::new()),
+            Err(QuranError::MorphologyNotLoaded)
+        ));
+
+        quran.attach_morphology(morphology).unwrap();
+        let mut search = QuranSearch::new(&quran);
+        let query = MorphologyQuery::new()
+            .root("ق-و-ل")
+            .unwrap()
+            .pos("V")
+            .aspect(Aspect::Imperfect);
+
+        assert_eq!(search.search_morphology(&query).unwrap().len(), 2);
+
+        let meccan = query.revelation_type(RevelationType::Meccan);
+        let search_results = search.search_morphology(&meccan).unwrap();
+        assert_eq!(search_results.len(), 1);
+        assert_eq!(search_results[0].ayah_number, 82);
+        assert_eq!(
+            crate::remove_diacritics(search_results[0].matched()[0]),
+            "يقول"
+        );
+    }
+
+    #[test]
+    fn test_search_normalizer() {
+        let quran = create_test_quran();
+
+        let mut search = QuranSearch::new(&quran);
+        assert!(search.search("وبالاخرة").is_empty());
+
+        let mut search = QuranSearch::new(&quran).with_normalizer(Normalizer::full());
+        let search_results = search.search("وبالاخرة");
+        assert!(search_results.iter().any(|hit| hit.ayah_number == 4));
+    }
+
+    #[test]
+    fn test_search_match_mode() {
+        let quran = create_test_quran();
+
+        let mut search = QuranSearch::new(&quran);
+        assert!(search.search_surah("علم", 1).unwrap().is_empty());
+
+        let mut search = QuranSearch::new(&quran).with_match_mode(MatchMode::Substring);
+        assert_eq!(search.search_surah("علم", 1).unwrap().len(), 1);
+
+        let mut search = QuranSearch::new(&quran).with_match_mode(MatchMode::Suffix);
+        assert_eq!(search.search_surah("لمين", 1).unwrap().len(), 1);
+
+        let mut search = QuranSearch::new(&quran).with_match_mode(MatchMode::Prefix);
+        assert_eq!(search.search_surah("ٱلرَّح", 1).unwrap().len(), 2);
+
+        let mut search = QuranSearch::new(&quran).with_match_mode(MatchMode::Stem);
+        assert_eq!(search.search_surah("الله", 1).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_search_query() {
+        let quran = create_test_quran();
+        let mut search = QuranSearch::new(&quran);
+
+        let both = search.search_query("الله AND الرحيم").unwrap();
+        let either = search.search_query("الله OR الرحيم").unwrap();
+        let without = search.search_query("الله NOT الرحيم").unwrap();
+        let allah = search.search("الله");
+        assert_eq!(both.len() + without.len(), allah.len());
+        assert!(either.len() >= allah.len());
+        assert!(either.windows(2).all(|pair| pair[0].index < pair[1].index));
+
+        let phrase = search
+            .search_query("\"بسم الله الرحمن\" NOT (سليمن OR إنه)")
+            .unwrap();
+        assert_eq!(phrase.len(), 1);
+        assert_eq!(phrase[0].ayah_number, 1);
+
+        assert!(search.search_query("(الله").is_err());
+    }
+
+    #[test]
+    fn test_search_near() {
+        let quran = create_test_quran();
+        let mut search = QuranSearch::new(&quran);
+
+        // الحمد in 1:2 right after the الرحيم ending 1:1.
+        let search_results = search.search_near("الرحيم", "الحمد", Proximity::Words(1));
+        assert_eq!(search_results[0].ayah_number, 1);
+        assert_eq!(search_results[1].ayah_number, 2);
+
+        let search_results = search
+            .search_query("ملك NEAR/2 الدين AND NOT \"بسم الله\"")
+            .unwrap();
+        assert_eq!(search_results.len(), 1);
+        assert_eq!(search_results[0].ayah_number, 4);
+        assert!(search.search_query("الله NEAR/4294967295 رب").is_err());
+        assert!(!search
+            .search_near("الله", "رب", Proximity::Words(u32::MAX))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_search_pattern() {
+        let quran = create_test_quran();
+        let search = QuranSearch::new(&quran);
+
+        // ٱلۡمَغۡضُوبِ in 1:7
+        let wildcard = Pattern::wildcard("المغض?ب").unwrap();
+        let matches = search.search_pattern(&wildcard);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].ayah.number(), 7);
+        assert_eq!(matches[0].word, 5);
+        assert_eq!(matches[0].matched, "المغضوب");
+
+        let matches = search.search_pattern(&Pattern::wildcard("مف?و?*").unwrap());
+        assert!(matches.iter().any(|m| m.matched == "مفعولا"));
+
+        let raw = QuranSearch::new(&quran).with_normalizer(Normalizer::none());
+        assert!(raw.search_pattern(&wildcard).is_empty());
+        let matches = raw.search_pattern(&Pattern::wildcard("ٱلمغض?ب").unwrap());
+        assert_eq!(matches[0].matched, "ٱلۡمَغۡضُوبِ");
+
+        // Spans point into the vowelled text: بِسۡمِ ٱللَّهِ ٱلرَّحۡمَٰنِ ٱلرَّحِيمِ
+        let regex = Pattern::regex("الرحمن ال(رحيم)").unwrap();
+        let matches = search.search_pattern(&regex);
+        assert_eq!(matches[0].ayah.number(), 1);
+        let (found, normalizer) = (&matches[0], Normalizer::new());
+        assert_eq!(normalizer.normalize(found.as_written()), "الرحمن الرحيم");
+        assert_eq!(found.span.bytes.end, found.text.len());
+        assert_eq!(
+            normalizer.normalize(&found.text[..found.span.bytes.start]),
+            "بسم الله "
+        );
+        assert_eq!(
+            found.span.chars.end - found.span.chars.start,
+            found.as_written().chars().count()
+        );
+        assert_eq!(found.word, 2);
+        let matches = search.search_pattern(&Pattern::regex("حم").unwrap());
+        assert_eq!(normalizer.normalize(matches[0].as_written()), "حم");
+        assert!(matches[0].as_written().chars().count() > 2);
+        assert_eq!(matches[0].word, 2);
+
+        // The wildcard is folded like the text.
+        for pattern in ["ٱلۡمَغۡض?ب", "المغض?ب"] {
+            let matches = search.search_pattern(&Pattern::wildcard(pattern).unwrap());
+            assert_eq!(matches.len(), 1, "{}", pattern);
+            assert_eq!(matches[0].as_written(), "ٱلۡمَغۡضُوبِ");
+        }
+        let hamza = QuranSearch::new(&quran).with_normalizer(Normalizer::full());
+        assert!(!hamza
+            .search_pattern(&Pattern::wildcard("إل?ك").unwrap())
+            .is_empty());
+
+        assert!(Pattern::regex("(").is_err());
+        assert!(Pattern::wildcard("بسم الله").is_err());
+    }
+
+    #[test]
+    fn test_search_fuzzy() {
+        let quran = create_test_quran();
+        let mut search = QuranSearch::new(&quran);
+
+        assert!(search.search("الصلاة").is_empty());
+
+        let matches = search.search_fuzzy("الصلاة", 0.5);
+        assert!(!matches.is_empty());
+        assert_eq!(matches[0].ayah.surah_id(), 2);
+        assert_eq!(matches[0].ayah.number(), 3);
+        assert_eq!(crate::remove_diacritics(&matches[0].matched), "الصلوة");
+        assert!(matches
+            .windows(2)
+            .all(|pair| pair[0].distance <= pair[1].distance));
+
+        // ذَٰلِكَ ٱلۡكِتَٰبُ: the dagger alef of ذٰلك costs a long vowel.
+        let matches = search.search_fuzzy("ذلك الكتاب", 0.5);
+        assert_eq!(matches[0].ayah.number(), 2);
+        assert_eq!(matches[0].word, 0);
+        assert_eq!(matches[0].distance, 0.5);
+    }
+
+    #[test]
+    fn test_search_ranked() {
+        let quran = create_test_quran();
+        let search = QuranSearch::new(&quran);
+
+        let hits = search.search_ranked("الرحمن الرحيم", RankUnit::Ayah);
+        assert!(hits.windows(2).all(|pair| pair[0].score >= pair[1].score));
+        // Both words in a short ayah: 1:3 ٱلرَّحۡمَٰنِ ٱلرَّحِيمِ.
+        assert_eq!(hits[0].surah.id(), 1);
+        assert_eq!(hits[0].ayahs[0].number(), 3);
+
+        // The rare word counts for more than the frequent one.
+        let hits = search.search_ranked("الله نستعين", RankUnit::Ayah);
+        assert_eq!(hits[0].ayahs[0].number(), 5);
+
+        let hits = search.search_ranked("الرحمن", RankUnit::Surah);
+        assert_eq!(hits[0].surah.id(), 19);
+        assert!(hits[0].ayahs.len() > 1);
+
+        let hits = search.search_ranked("الرحمن", RankUnit::Division(Division::Page));
+        assert!(hits.windows(2).all(|pair| pair[0].score >= pair[1].score));
+        for hit in &hits {
+            let page = hit.ayahs[0].divisions().page;
+            assert!(hit.ayahs.iter().all(|ayah| ayah.divisions().page == page));
+        }
+        let pages: std::collections::HashSet<u32> = hits
+            .iter()
+            .map(|hit| hit.ayahs[0].divisions().page)
+            .collect();
+        assert_eq!(pages.len(), hits.len());
+        // Page 1 is al-Fatihah.
+        assert!(hits.iter().any(|hit| hit.ayahs[0].divisions().page == 1));
+    }
+
+    #[test]
+    fn test_search_multiple_terms() {
+        let quran = create_test_quran();
+        let mut search = QuranSearch::new(&quran);
+
+        let search_results = search.search_multiple_terms(vec!["الرحمن", "الرحيم"]);
+        assert_eq!(search_results[0].ayah_number, 1);
+        assert_eq!(search_results[1].ayah_number, 3);
+        assert!(search_results
+            .windows(2)
+            .all(|pair| pair[0].index < pair[1].index));
+    }
+
+    #[test]
+    fn test_search_layer() {
+        let quran = create_test_quran();
+
+        let mut search = QuranSearch::new(&quran)
+            .with_layer(TextLayer::SimpleClean)
+            .unwrap();
+        let search_results = search.search_surah("الحمد لله", 1).unwrap();
+        assert_eq!(search_results.len(), 1);
+        assert_eq!(search_results[0].text, "الحمد لله رب العلمين");
+
+        assert!(QuranSearch::new(&quran)
+            .with_layer(TextLayer::Simple)
+            .is_err());
+    }
+
+    #[test]
+    fn test_search_translation() {
+        let quran = create_test_quran();
+        let english = TextLayer::Translation("en".to_string());
+        let mut search = QuranSearch::new(&quran).with_layer(english).unwrap();
+
+        // "help" in 1:5, with the Arabic alongside.
+        let hits = search.search_surah("Helping", 1).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].ayah_number, 5);
+        assert_eq!(hits[0].text, hits[0].ayah.translation().unwrap());
+        assert_eq!(hits[0].matched(), vec!["help"]);
+        assert!(hits[0].ayah.text().contains('\u{0646}'));
+
+        // Stop words in a phrase match any word.
+        let hits = search.search_surah("lords of THE world", 1).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(
+            hits[0]
+                .highlight(Highlighter::Markdown)
+                .matches("**")
+                .count(),
+            2
+        );
+
+        let hits = search.search_query("mercy AND NOT praise").unwrap();
+        assert!(hits
+            .iter()
+            .any(|hit| hit.surah_id == 1 && hit.ayah_number == 3));
+        assert!(!hits
+            .iter()
+            .any(|hit| hit.surah_id == 1 && hit.ayah_number == 2));
+
+        let ranked = search.search_ranked("worship help", RankUnit::Ayah);
+        assert_eq!(ranked[0].ayahs[0].number(), 5);
+    }
+
+    #[test]
+    fn test_search_ayahs_keeps_translation() {
+        let quran = create_test_quran();
+        let search = QuranSearch::new(&quran);
+
+        let ayahs = search.search_ayahs("نستعين");
+        assert_eq!(ayahs.len(), 1);
+        assert_eq!(
+            ayahs[0].translation(),
+            Some("It is You we worship and You we ask for help")
+        );
+    }
+
     #[test]
     fn test_search_surah() {
         // Create a test Quran instance
@@ -130,22 +725,100 @@ mod tests {
         let mut search = QuranSearch::new(&quran);
 
         // Perform the search within a specific surah
-        let search_results = search.search_surah("الله", 1);
+        let search_results = search.search_surah("الله", 1).unwrap();
 
         // Assert the expected results
         assert_eq!(search_results.len(), 1);
         // Add more assertions as needed based on your test case
+
+        assert!(matches!(
+            search.search_surah("الله", 0),
+            Err(QuranError::SurahOutOfRange { surah: 0, .. })
+        ));
+        assert!(search.search_surah("الله", 115).is_err());
     }
 
     #[test]
     fn test_search_range() {
         let quran = create_test_quran();
-
-        // Search for verses in the range 1-5 containing the term "Allah"
         let mut search = QuranSearch::new(&quran);
 
-        let search_results = search.search_range(1, 5, "الله");
+        let search_results = search
+            .search_range(VerseRef::new(1, 1), VerseRef::new(1, 5), "الله")
+            .unwrap();
+        assert_eq!(search_results.len(), 1);
+        assert_eq!(search_results[0].ayah_number, 1);
+
+        // 1:7 to 2:2, across the end of Al-Fatihah.
+        let search_results = search
+            .search_range(VerseRef::new(1, 7), VerseRef::new(2, 2), "الضالين")
+            .unwrap();
+        assert_eq!(search_results[0].ayah_number, 7);
+        let search_results = search
+            .search_range(VerseRef::new(1, 7), VerseRef::new(2, 2), "ذلك")
+            .unwrap();
+        assert_eq!(
+            (search_results[0].surah_id, search_results[0].ayah_number),
+            (2, 2)
+        );
+
+        assert!(matches!(
+            search.search_range(VerseRef::new(2, 1), VerseRef::new(1, 1), "الله"),
+            Err(QuranError::InvalidReference(_))
+        ));
+        assert!(matches!(
+            search.search_range(VerseRef::new(1, 1), VerseRef::new(1, 8), "الله"),
+            Err(QuranError::AyahOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn test_search_global_range() {
+        let quran = create_test_quran();
+        let mut search = QuranSearch::new(&quran);
 
+        // Positions 1 to 5 are 1:2 to 1:6.
+        let search_results = search.search_global_range(1, 5, "الله");
         assert_eq!(search_results.len(), 0);
+
+        // 2:2 is at position 8.
+        assert!(search.search_global_range(0, 7, "ذلك").is_empty());
+        assert_eq!(search.search_global_range(0, 8, "ذلك")[0].index, 8);
+    }
+
+    #[test]
+    fn test_search_scope() {
+        let quran = create_test_quran();
+
+        let search = QuranSearch::new(&quran)
+            .with_scope(Scope::Division(Division::Page, 604))
+            .unwrap();
+        let ayahs = search.search_ayahs("الله");
+        let refs: Vec<String> = ayahs
+            .iter()
+            .map(|ayah| ayah.verse_ref().to_string())
+            .collect();
+        assert_eq!(refs, vec!["112:1", "112:2"]);
+
+        let mut search = QuranSearch::new(&quran)
+            .with_scope(Scope::Division(Division::Juz, 30))
+            .unwrap();
+        let hits = search.search("الله");
+        assert!(!hits.is_empty());
+        assert!(hits.iter().all(|hit| hit.surah_id >= 78));
+        assert!(search
+            .search_ranked("الله", RankUnit::Surah)
+            .iter()
+            .all(|hit| hit.surah.id() >= 78));
+
+        let search = QuranSearch::new(&quran)
+            .with_scope(Scope::Surah(1))
+            .unwrap();
+        assert!(search.search_fuzzy("الصلاة", 1.0).is_empty());
+
+        assert!(matches!(
+            QuranSearch::new(&quran).with_scope(Scope::Division(Division::Page, 605)),
+            Err(QuranError::DivisionOutOfRange { number: 605, .. })
+        ));
     }
 }
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use crate::{error::QuranError, quran::surah::Surah};
+
+use super::{assemble, parse_number, CorpusSource, VerseEntry};
+
+/// A simple CSV layout: `surah,ayah,text[,translation]`.
+///
+/// Fields may be quoted with `"` (doubling a quote escapes it), and a leading
+/// header row is skipped when its first column is not a number.
+#[derive(Debug, Clone, Copy)]
+pub struct CsvSource {
+    pub delimiter: char,
+}
+
+impl Default for CsvSource {
+    fn default() -> Self {
+        Self { delimiter: ',' }
+    }
+}
+
+impl CorpusSource for CsvSource {
+    fn parse(&self, input: &str, origin: &str) -> Result<Vec<Surah>, QuranError> {
+        let mut entries = Vec::new();
+
+        for (index, (line, fields)) in self.records(input, origin)?.into_iter().enumerate() {
+            if fields.iter().all(|field| field.trim().is_empty()) {
+                continue;
+            }
+
+            let is_header = index == 0 && fields[0].trim().parse::<u32>().is_err();
+            if is_header {
+                continue;
+            }
+
+            if fields.len() < 3 {
+                return Err(QuranError::FormatError {
+                    origin: origin.to_string(),
+                    line,
+                    message: format!("expected at least 3 columns, found {}", fields.len()),
+                });
+            }
+
+            let mut fields = fields.into_iter();
+            let surah = parse_number(&fields.next().unwrap_or_default(), "surah", origin, line)?;
+            let ayah = parse_number(&fields.next().unwrap_or_default(), "ayah", origin, line)?;
+            let text = fields.next().unwrap_or_default();
+            let translation = fields.next().filter(|value| !value.is_empty());
+
+            entries.push(VerseEntry {
+                surah,
+                ayah,
+                text,
+                translation,
+                line,
+            });
+        }
+
+        assemble(entries, &HashMap::new())
+    }
+}
+
+impl CsvSource {
+    /// Splits `input` into records, returning each with the line it starts on.
+    fn records(&self, input: &str, origin: &str) -> Result<Vec<(usize, Vec<String>)>, QuranError> {
+        let mut records = Vec::new();
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut in_quotes = false;
+        let mut line = 1;
+        let mut record_line = 1;
+        let mut chars = input.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if in_quotes => {
+                    if chars.peek() == Some(&'"') {
+                        chars.next();
+                        field.push('"');
+                    } else {
+                        in_quotes = false;
+                    }
+                }
+                '"' if field.is_empty() => in_quotes = true,
+                '\n' if in_quotes => {
+                    line += 1;
+                    field.push(c);
+                }
+                '\n' => {
+                    fields.push(std::mem::take(&mut field));
+                    records.push((record_line, std::mem::take(&mut fields)));
+                    line += 1;
+                    record_line = line;
+                }
+                '\r' if !in_quotes && chars.peek() == Some(&'\n') => {}
+                c if c == self.delimiter && !in_quotes => {
+                    fields.push(std::mem::take(&mut field));
+                }
+                c => field.push(c),
+            }
+        }
+
+        if in_quotes {
+            return Err(QuranError::FormatError {
+                origin: origin.to_string(),
+                line: record_line,
+                message: "unterminated quoted field".to_string(),
+            });
+        }
+
+        if !field.is_empty() || !fields.is_empty() {
+            fields.push(field);
+            records.push((record_line, fields));
+        }
+
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv() {
+        let input = "surah,ayah,text,translation\n\
+                     1,1,بِسْمِ اللَّهِ,\"In the name of Allah, the Merciful\"\n\
+                     1,2,\"الْحَمْدُ لِلَّهِ\",\"Praise, \"\"all\"\" of it\"\n";
+        let surahs = CsvSource::default().parse(input, "<test>").unwrap();
+
+        assert_eq!(surahs.len(), 1);
+        let ayahs = surahs[0].ayahs();
+        assert_eq!(ayahs.len(), 2);
+        assert_eq!(ayahs[0].text(), "بِسْمِ اللَّهِ");
+        assert_eq!(
+            ayahs[0].translation(),
+            Some("In the name of Allah, the Merciful")
+        );
+        assert_eq!(ayahs[1].translation(), Some("Praise, \"all\" of it"));
+    }
+
+    #[test]
+    fn test_csv_bad_number() {
+        let input = "1,1,بِسْمِ\nx,2,اللَّهِ\n";
+        match CsvSource::default().parse(input, "surahs.csv") {
+            Err(error @ QuranError::FormatError { line: 2, .. }) => {
+                assert!(error.to_string().starts_with("surahs.csv, line 2:"));
+            }
+            other => panic!("unexpected result: {:?}", other.err()),
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use serde::Deserialize;
+
+use crate::{
+    error::QuranError,
+    quran::{
+        surah::{RevelationType, Surah},
+        verse::Ayah,
+    },
+};
+
+use super::CorpusSource;
+
+/// The layout of the bundled `quran.json`: an array of surahs, each holding
+/// its `verses` along with optional translation and revelation metadata.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonSource;
+
+impl CorpusSource for JsonSource {
+    fn parse(&self, input: &str, origin: &str) -> Result<Vec<Surah>, QuranError> {
+        let deserializer = &mut serde_json::Deserializer::from_str(input);
+        let records: Vec<SurahRecord> =
+            serde_path_to_error::deserialize(deserializer).map_err(|e| {
+                let path = e.path().to_string();
+                let inner = e.into_inner();
+                if inner.is_data() {
+                    schema_error(&path, inner.to_string())
+                } else {
+                    QuranError::JsonError(format!("{}: {}", origin, inner))
+                }
+            })?;
+
+        parse_surahs(records, origin)
+    }
+}
+
+/// A surah as laid out in `quran.json`.
+#[derive(Debug, Deserialize)]
+struct SurahRecord {
+    id: u32,
+    name: String,
+    transliteration: Option<String>,
+    translation: Option<String>,
+    #[serde(rename = "type")]
+    revelation_type: Option<String>,
+    total_verses: u32,
+    verses: Vec<VerseRecord>,
+}
+
+/// A verse as laid out in `quran.json`.
+#[derive(Debug, Deserialize)]
+struct VerseRecord {
+    id: u32,
+    text: String,
+    translation: Option<String>,
+}
+
+fn parse_surahs(records: Vec<SurahRecord>, origin: &str) -> Result<Vec<Surah>, QuranError> {
+    let mut parsed_surahs = Vec::new();
+
+    for (surah_index, surah) in records.into_iter().enumerate() {
+        let expected_id = surah_index as u32 + 1;
+        if surah.id != expected_id {
+            return Err(QuranError::NonContiguousId {
+                path: format!("[{}].id", surah_index),
+                surah: expected_id,
+                verse: None,
+                expected: expected_id,
+                found: surah.id,
+            });
+        }
+
+        if surah.total_verses as usize != surah.verses.len() {
+            return Err(QuranError::VerseCountMismatch {
+                origin: origin.to_string(),
+                path: format!("[{}].total_verses", surah_index),
+                surah: surah.id,
+                expected: surah.total_verses as usize,
+                found: surah.verses.len(),
+            });
+        }
+
+        let revelation_type = match surah.revelation_type.as_deref() {
+            Some(value) => Some(RevelationType::parse(value).ok_or_else(|| {
+                schema_error(
+                    &format!("[{}].type", surah_index),
+                    format!("unknown revelation type `{}`", value),
+                )
+            })?),
+            None => None,
+        };
+
+        let mut parsed_ayahs = Vec::new();
+
+        for (verse_index, ayah) in surah.verses.into_iter().enumerate() {
+            let expected_id = verse_index as u32 + 1;
+            if ayah.id != expected_id {
+                return Err(QuranError::NonContiguousId {
+                    path: format!("[{}].verses[{}].id", surah_index, verse_index),
+                    surah: surah.id,
+                    verse: Some(verse_index),
+                    expected: expected_id,
+                    found: ayah.id,
+                });
+            }
+
+            parsed_ayahs.push(Ayah::new(
+                ayah.id,
+                surah.id,
+                surah.name.clone(),
+                ayah.text,
+                ayah.translation,
+            ));
+        }
+
+        parsed_surahs.push(Surah {
+            id: surah.id,
+            name: surah.name,
+            transliteration: surah.transliteration,
+            translation: surah.translation,
+            revelation_type,
+            total_verses: surah.total_verses,
+            ayahs: parsed_ayahs,
+        });
+    }
+
+    Ok(parsed_surahs)
+}
+
+/// Builds a [`QuranError::SchemaError`] from a JSON path such as
+/// `[3].verses[5].text`, recovering the surah/verse positions and field name.
+fn schema_error(path: &str, message: String) -> QuranError {
+    let mut surah = None;
+    let mut verse = None;
+    let mut field = None;
+
+    for segment in path.split('.') {
+        let (name, index) = match segment.find('[') {
+            Some(open) => (
+                &segment[..open],
+                segment[open + 1..].trim_end_matches(']').parse().ok(),
+            ),
+            None => (segment, None),
+        };
+
+        match name {
+            "" => surah = index,
+            "verses" => verse = index,
+            "?" => {}
+            name => field = Some(name.to_string()),
+        }
+    }
+
+    // Missing fields are reported against the enclosing object.
+    if let Some(missing) = message
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split('`').next())
+    {
+        field = Some(missing.to_string());
+    }
+
+    QuranError::SchemaError {
+        path: path.to_string(),
+        surah,
+        verse,
+        field,
+        message,
+    }
+}
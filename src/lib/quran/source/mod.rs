@@ -0,0 +1,111 @@
+//! Readers that turn the various Quran text formats into the common
+//! [`Surah`]/[`Ayah`] model.
+
+mod csv;
+mod json;
+mod tanzil;
+
+use std::collections::HashMap;
+
+pub use self::csv::CsvSource;
+pub use self::json::JsonSource;
+pub use self::tanzil::{TanzilTextSource, TanzilXmlSource};
+
+use crate::error::QuranError;
+
+use super::{meta, surah::Surah, verse::Ayah};
+
+/// A format the Quran text can be loaded from.
+///
+/// `origin` names where `input` came from (a path, `<reader>`, ...) and is
+/// only used to label error messages.
+pub trait CorpusSource {
+    fn parse(&self, input: &str, origin: &str) -> Result<Vec<Surah>, QuranError>;
+}
+
+/// A single verse read from a line- or element-oriented source.
+struct VerseEntry {
+    surah: u32,
+    ayah: u32,
+    text: String,
+    translation: Option<String>,
+    line: usize,
+}
+
+/// Groups verses into surahs, checking that surah and verse numbers run
+/// contiguously from 1. Names default to [`meta::SURAHS`] unless the source
+/// supplied its own in `names`.
+fn assemble<I>(entries: I, names: &HashMap<u32, String>) -> Result<Vec<Surah>, QuranError>
+where
+    I: IntoIterator<Item = VerseEntry>,
+{
+    let mut surahs: Vec<Surah> = Vec::new();
+
+    for entry in entries {
+        let current_id = surahs.last().map_or(0, |surah| surah.id);
+
+        if entry.surah != current_id {
+            if entry.surah != current_id + 1 {
+                return Err(QuranError::NonContiguousId {
+                    path: format!("line {}", entry.line),
+                    surah: current_id + 1,
+                    verse: None,
+                    expected: current_id + 1,
+                    found: entry.surah,
+                });
+            }
+            surahs.push(new_surah(entry.surah, names));
+        }
+
+        let surah_index = surahs.len() - 1;
+        let surah = &mut surahs[surah_index];
+        let expected_ayah = surah.ayahs.len() as u32 + 1;
+        if entry.ayah != expected_ayah {
+            return Err(QuranError::NonContiguousId {
+                path: format!("line {}", entry.line),
+                surah: surah.id,
+                verse: Some(surah.ayahs.len()),
+                expected: expected_ayah,
+                found: entry.ayah,
+            });
+        }
+
+        surah.ayahs.push(Ayah::new(
+            entry.ayah,
+            surah.id,
+            surah.name.clone(),
+            entry.text,
+            entry.translation,
+        ));
+        surah.total_verses = expected_ayah;
+    }
+
+    Ok(surahs)
+}
+
+fn new_surah(id: u32, names: &HashMap<u32, String>) -> Surah {
+    let info = meta::surah_info(id);
+    let name = match (names.get(&id), info) {
+        (Some(name), _) => name.clone(),
+        (None, Some(info)) => info.name.to_string(),
+        (None, None) => id.to_string(),
+    };
+
+    Surah {
+        id,
+        name,
+        transliteration: info.map(|info| info.transliteration.to_string()),
+        translation: info.map(|info| info.translation.to_string()),
+        revelation_type: info.map(|info| info.revelation_type),
+        total_verses: 0,
+        ayahs: Vec::new(),
+    }
+}
+
+fn parse_number(value: &str, what: &str, origin: &str, line: usize) -> Result<u32, QuranError> {
+    value.trim().parse().map_err(|_| QuranError::FormatError {
+        origin: origin.to_string(),
+        line,
+        message: format!("invalid {} number `{}`", what, value.trim()),
+    })
+}
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+
+use crate::{error::QuranError, quran::surah::Surah};
+
+use super::{assemble, parse_number, CorpusSource, VerseEntry};
+
+/// Tanzil's plain-text export: one `sura|aya|text` line per verse.
+///
+/// Blank lines and the `#` comment block Tanzil appends are skipped.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TanzilTextSource;
+
+impl CorpusSource for TanzilTextSource {
+    fn parse(&self, input: &str, origin: &str) -> Result<Vec<Surah>, QuranError> {
+        let mut entries = Vec::new();
+
+        for (index, line) in input.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.splitn(3, '|');
+            let (surah, ayah, text) = match (fields.next(), fields.next(), fields.next()) {
+                (Some(surah), Some(ayah), Some(text)) => (surah, ayah, text),
+                _ => {
+                    return Err(QuranError::FormatError {
+                        origin: origin.to_string(),
+                        line: line_number,
+                        message: "expected `sura|aya|text`".to_string(),
+                    })
+                }
+            };
+
+            entries.push(VerseEntry {
+                surah: parse_number(surah, "sura", origin, line_number)?,
+                ayah: parse_number(ayah, "aya", origin, line_number)?,
+                text: text.trim().to_string(),
+                translation: None,
+                line: line_number,
+            });
+        }
+
+        assemble(entries, &HashMap::new())
+    }
+}
+
+/// Tanzil's XML export: `<sura index name>` elements holding
+/// `<aya index text>` elements.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TanzilXmlSource;
+
+impl CorpusSource for TanzilXmlSource {
+    fn parse(&self, input: &str, origin: &str) -> Result<Vec<Surah>, QuranError> {
+        let document = roxmltree::Document::parse(input).map_err(|e| QuranError::FormatError {
+            origin: origin.to_string(),
+            line: e.pos().row as usize,
+            message: e.to_string(),
+        })?;
+
+        let mut entries = Vec::new();
+        let mut names = HashMap::new();
+
+        for sura in document
+            .root_element()
+            .children()
+            .filter(|node| node.has_tag_name("sura"))
+        {
+            let line = document.text_pos_at(sura.range().start).row as usize;
+            let surah = parse_number(
+                attribute(&sura, "index", origin, line)?,
+                "sura",
+                origin,
+                line,
+            )?;
+            if let Some(name) = sura.attribute("name") {
+                names.insert(surah, name.to_string());
+            }
+
+            for aya in sura.children().filter(|node| node.has_tag_name("aya")) {
+                let line = document.text_pos_at(aya.range().start).row as usize;
+                entries.push(VerseEntry {
+                    surah,
+                    ayah: parse_number(
+                        attribute(&aya, "index", origin, line)?,
+                        "aya",
+                        origin,
+                        line,
+                    )?,
+                    text: attribute(&aya, "text", origin, line)?.to_string(),
+                    translation: None,
+                    line,
+                });
+            }
+        }
+
+        assemble(entries, &names)
+    }
+}
+
+fn attribute<'a>(
+    node: &roxmltree::Node<'a, '_>,
+    name: &str,
+    origin: &str,
+    line: usize,
+) -> Result<&'a str, QuranError> {
+    node.attribute(name).ok_or_else(|| QuranError::FormatError {
+        origin: origin.to_string(),
+        line,
+        message: format!(
+            "<{}> is missing the `{}` attribute",
+            node.tag_name().name(),
+            name
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tanzil_text() {
+        let input = "1|1|بِسْمِ اللَّهِ الرَّحْمَٰنِ الرَّحِيمِ\n\
+                     1|2|الْحَمْدُ لِلَّهِ رَبِّ الْعَالَمِينَ\n\
+                     2|1|الم\n\
+                     \n\
+                     # Tanzil Quran Text\n";
+        let surahs = TanzilTextSource.parse(input, "<test>").unwrap();
+
+        assert_eq!(surahs.len(), 2);
+        assert_eq!(surahs[0].name(), "الفاتحة");
+        assert_eq!(surahs[0].total_ayahs(), 2);
+        assert_eq!(surahs[1].ayahs()[0].text(), "الم");
+    }
+
+    #[test]
+    fn test_tanzil_text_gap() {
+        let input = "1|1|بِسْمِ اللَّهِ\n1|3|الرَّحْمَٰنِ";
+        assert!(matches!(
+            TanzilTextSource.parse(input, "<test>"),
+            Err(QuranError::NonContiguousId {
+                surah: 1,
+                expected: 2,
+                found: 3,
+                ..
+            })
+        ));
+
+        let input = "1|1|بِسْمِ اللَّهِ\n3|1|الم";
+        assert!(matches!(
+            TanzilTextSource.parse(input, "<test>"),
+            Err(QuranError::NonContiguousId {
+                surah: 2,
+                verse: None,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_tanzil_xml() {
+        let input = r#"<?xml version="1.0" encoding="utf-8" ?>
+<quran>
+  <sura index="1" name="الفاتحة">
+    <aya index="1" text="بِسْمِ اللَّهِ الرَّحْمَٰنِ الرَّحِيمِ" />
+    <aya index="2" text="الْحَمْدُ لِلَّهِ رَبِّ الْعَالَمِينَ" />
+  </sura>
+</quran>"#;
+        let surahs = TanzilXmlSource.parse(input, "<test>").unwrap();
+
+        assert_eq!(surahs.len(), 1);
+        assert_eq!(surahs[0].ayahs().len(), 2);
+        assert_eq!(surahs[0].transliteration(), Some("Al-Fatihah"));
+    }
+
+    #[test]
+    fn test_tanzil_xml_missing_text() {
+        let input = "<quran>\n<sura index=\"1\">\n<aya index=\"1\" />\n</sura>\n</quran>";
+        match TanzilXmlSource.parse(input, "quran-simple.xml") {
+            Err(QuranError::FormatError { origin, line, .. }) => {
+                assert_eq!(origin, "quran-simple.xml");
+                assert_eq!(line, 3);
+            }
+            other => panic!("unexpected result: {:?}", other.err()),
+        }
+    }
+}
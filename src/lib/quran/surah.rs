@@ -1,11 +1,50 @@
-use crate::traits::{TotalLetters, TotalWords};
+use std::fmt;
 
-use super::verse::Ayah;
+use crate::{
+    error::QuranError,
+    letters::LetterPolicy,
+    traits::{TotalLetters, TotalWords},
+};
+
+use super::{
+    meta::{self, surah_info},
+    verse::Ayah,
+};
+
+/// Where a surah was revealed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RevelationType {
+    Meccan,
+    Medinan,
+}
+
+impl RevelationType {
+    /// Parses the `type` field used by `quran.json` ("meccan" / "medinan").
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "meccan" | "makki" => Some(Self::Meccan),
+            "medinan" | "madani" => Some(Self::Medinan),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RevelationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Meccan => write!(f, "Meccan"),
+            Self::Medinan => write!(f, "Medinan"),
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct Surah {
     pub(in crate::quran) id: u32,
     pub(in crate::quran) name: String,
+    pub(in crate::quran) transliteration: Option<String>,
+    pub(in crate::quran) translation: Option<String>,
+    pub(in crate::quran) revelation_type: Option<RevelationType>,
     pub(in crate::quran) total_verses: u32,
     pub(in crate::quran) ayahs: Vec<Ayah>,
 }
@@ -15,6 +54,31 @@ impl Surah {
         self.name.to_owned()
     }
 
+    /// Latin transliteration of the name, e.g. "Al-Fatihah".
+    pub fn transliteration(&self) -> Option<&str> {
+        self.transliteration.as_deref()
+    }
+
+    /// English rendering of the name, e.g. "The Opener".
+    pub fn translation(&self) -> Option<&str> {
+        self.translation.as_deref()
+    }
+
+    pub fn revelation_type(&self) -> Option<RevelationType> {
+        self.revelation_type
+    }
+
+    /// Position in the order of revelation, from 1 to 114, for the surahs
+    /// of the canonical numbering.
+    pub fn revelation_order(&self) -> Option<u32> {
+        surah_info(self.id).map(|info| info.revelation_order)
+    }
+
+    /// The disjoined letters the surah opens with, e.g. "الم" or "حم".
+    pub fn muqattaat(&self) -> Option<&'static str> {
+        meta::muqattaat(self.id)
+    }
+
     pub fn id(&self) -> u32 {
         self.id
     }
@@ -26,11 +90,25 @@ impl Surah {
     pub fn total_ayahs(&self) -> usize {
         self.total_verses as usize
     }
+
+    /// Ayah `ayah_number`, counting from 1.
+    pub fn get_ayah(&self, ayah_number: usize) -> Result<&Ayah, QuranError> {
+        self.ayahs
+            .get(ayah_number.wrapping_sub(1))
+            .ok_or(QuranError::AyahOutOfRange {
+                surah: self.id,
+                ayah: ayah_number as u32,
+                ayahs: self.ayahs.len(),
+            })
+    }
 }
 
 impl TotalLetters for Surah {
-    fn total_letters(&self) -> usize {
-        self.ayahs.iter().map(|verse| verse.total_letters()).sum()
+    fn total_letters_with(&self, policy: &LetterPolicy) -> usize {
+        self.ayahs
+            .iter()
+            .map(|verse| verse.total_letters_with(policy))
+            .sum()
     }
 }
 
@@ -1,24 +1,105 @@
+use std::collections::HashMap;
+
 use crate::{
-    remove_diacritics,
+    letters::LetterPolicy,
+    normalizer::{default_normalizer, Normalizer},
+    tokenizer::{Token, Tokenizer},
     traits::{TotalLetters, TotalWords},
 };
 
+use super::{
+    division::{self, Divisions, SajdahKind},
+    layer::TextLayer,
+    matching::{matches_words, MatchMode},
+    reference::VerseRef,
+};
+
 #[derive(Debug)]
 pub struct Ayah {
     pub(in crate::quran) ayah_number: u32,
+    pub(in crate::quran) surah_id: u32,
     pub(in crate::quran) surah_name: String,
     pub(in crate::quran) text: String,
+    /// The layer `text` belongs to.
+    pub(in crate::quran) layer: TextLayer,
+    /// Every other layer attached to this ayah.
+    pub(in crate::quran) layers: HashMap<TextLayer, String>,
 }
 
 impl Ayah {
+    pub(in crate::quran) fn new(
+        ayah_number: u32,
+        surah_id: u32,
+        surah_name: String,
+        text: String,
+        translation: Option<String>,
+    ) -> Self {
+        let layers = translation
+            .map(|translation| (TextLayer::Translation("en".to_string()), translation))
+            .into_iter()
+            .collect();
+        Self {
+            ayah_number,
+            surah_id,
+            surah_name,
+            text,
+            layer: TextLayer::default(),
+            layers,
+        }
+    }
+
     pub fn words(&self) -> Vec<&str> {
-        self.text.trim().split_whitespace().collect()
+        self.text.split_whitespace().collect()
+    }
+
+    /// Words of the ayah in `layer`, or none when the layer is not attached.
+    pub fn words_in(&self, layer: &TextLayer) -> Vec<&str> {
+        self.text_in(layer)
+            .map(|text| text.split_whitespace().collect())
+            .unwrap_or_default()
+    }
+
+    /// Segmented words of the ayah, with spans into [`Ayah::text`].
+    pub fn tokens(&self) -> Vec<Token<'_>> {
+        Tokenizer::new().tokenize(&self.text)
     }
 
+    /// Segmented words of the ayah in `layer`, or none when the layer is not
+    /// attached.
+    pub fn tokens_in(&self, layer: &TextLayer) -> Vec<Token<'_>> {
+        self.text_in(layer)
+            .map(|text| Tokenizer::new().tokenize(text))
+            .unwrap_or_default()
+    }
+
+    /// The text as it was loaded.
     pub fn text(&self) -> &str {
         self.text.as_str()
     }
 
+    /// The text in `layer`, if that layer is attached.
+    pub fn text_in(&self, layer: &TextLayer) -> Option<&str> {
+        if *layer == self.layer {
+            Some(self.text.as_str())
+        } else {
+            self.layers.get(layer).map(String::as_str)
+        }
+    }
+
+    /// English translation of the ayah, when the source provides one: the
+    /// text of the `Translation("en")` layer.
+    pub fn translation(&self) -> Option<&str> {
+        self.layers
+            .iter()
+            .find(|(layer, _)| layer.translation_language() == Some("en"))
+            .map(|(_, text)| text.as_str())
+            .filter(|text| !text.is_empty())
+    }
+
+    pub fn surah_id(&self) -> u32 {
+        self.surah_id
+    }
+
     pub fn surah_name(&self) -> &str {
         self.surah_name.as_str()
     }
@@ -27,16 +108,107 @@ impl Ayah {
         self.ayah_number as usize
     }
 
+    pub fn verse_ref(&self) -> VerseRef {
+        VerseRef::new(self.surah_id, self.ayah_number)
+    }
+
+    /// The juz', page and other divisions the ayah belongs to.
+    pub fn divisions(&self) -> Divisions {
+        Divisions::of(self.verse_ref())
+    }
+
+    /// Whether this is an ayah of prostration, and of which kind.
+    pub fn sajdah(&self) -> Option<SajdahKind> {
+        division::sajdah(self.verse_ref()).map(|sajdah| sajdah.kind)
+    }
+
+    /// Whether `search_term` occurs as whole words, ignoring diacritics.
     pub fn contains_word(&self, search_term: &str) -> bool {
-        let search_term = &remove_diacritics(search_term);
-        let stripped = remove_diacritics(&self.text);
-        stripped.contains(search_term)
+        self.contains_word_in(search_term, &self.layer)
+    }
+
+    /// Like [`Ayah::contains_word`], but matches against `layer`.
+    pub fn contains_word_in(&self, search_term: &str, layer: &TextLayer) -> bool {
+        self.contains_word_with(search_term, layer, default_normalizer())
+    }
+
+    /// Like [`Ayah::contains_word_in`], but folds both sides with `normalizer`.
+    pub fn contains_word_with(
+        &self,
+        search_term: &str,
+        layer: &TextLayer,
+        normalizer: &Normalizer,
+    ) -> bool {
+        self.matches_in(search_term, layer, MatchMode::Exact, normalizer)
+    }
+
+    /// Whether the stems of `search_term` occur as consecutive words in
+    /// `layer`, so that "الله" also finds "لِلَّهِ" and "بِٱللَّهِ".
+    pub fn contains_stem_in(&self, search_term: &str, layer: &TextLayer) -> bool {
+        self.contains_stem_with(search_term, layer, default_normalizer())
+    }
+
+    /// Like [`Ayah::contains_stem_in`], but folds the stems with `normalizer`.
+    pub fn contains_stem_with(
+        &self,
+        search_term: &str,
+        layer: &TextLayer,
+        normalizer: &Normalizer,
+    ) -> bool {
+        self.matches_in(search_term, layer, MatchMode::Stem, normalizer)
+    }
+
+    /// Matches `search_term` against the text in `layer` under `mode`, after
+    /// folding both with `normalizer`.
+    pub fn matches_in(
+        &self,
+        search_term: &str,
+        layer: &TextLayer,
+        mode: MatchMode,
+        normalizer: &Normalizer,
+    ) -> bool {
+        let Some(text) = self.text_in(layer) else {
+            return false;
+        };
+
+        match mode {
+            MatchMode::Substring => {
+                let search_term = normalizer.normalize(search_term);
+                !search_term.is_empty() && normalizer.normalize(text).contains(&search_term)
+            }
+            MatchMode::Stem => {
+                let tokenizer = Tokenizer::new();
+                let term_stems: Vec<String> = tokenizer
+                    .tokenize(search_term)
+                    .into_iter()
+                    .map(|token| normalizer.normalize(&token.stem))
+                    .collect();
+                let stems: Vec<String> = tokenizer
+                    .tokenize(text)
+                    .into_iter()
+                    .map(|token| normalizer.normalize(&token.stem))
+                    .collect();
+                matches_words(&stems, &term_stems, mode)
+            }
+            _ => {
+                let search_term = normalizer.normalize(search_term);
+                let text = normalizer.normalize(text);
+                let terms: Vec<&str> = search_term.split_whitespace().collect();
+                let words: Vec<&str> = text.split_whitespace().collect();
+                matches_words(&words, &terms, mode)
+            }
+        }
+    }
+
+    /// Number of letters of the ayah in `layer` under `policy`.
+    pub fn total_letters_in(&self, layer: &TextLayer, policy: &LetterPolicy) -> usize {
+        self.text_in(layer).map_or(0, |text| policy.count(text))
     }
 }
 
 impl TotalLetters for Ayah {
-    fn total_letters(&self) -> usize {
-        self.text.chars().count()
+    fn total_letters_with(&self, policy: &LetterPolicy) -> usize {
+        policy.count(&self.text)
     }
 }
 
@@ -0,0 +1,393 @@
+//! Trilateral root (jidhr) extraction in the style of the Khoja stemmer.
+//!
+//! A word is first stripped of its clitics by the [tokenizer](crate::tokenizer),
+//! then matched against tables of derivational patterns written with the
+//! placeholder letters ف ع ل. Pattern letters outside the placeholders must
+//! match exactly; the letters under ف, ع and ل form the root. Suffixes are
+//! peeled off one at a time until a pattern fits, and two-letter remainders
+//! are completed as geminate or hollow roots.
+//!
+//! There is no root dictionary behind this, so the result is a best guess.
+
+use std::{fmt, str::FromStr};
+
+use crate::tokenizer::Tokenizer;
+
+const DAGGER_ALEF: char = '\u{0670}';
+const SHADDA: char = '\u{0651}';
+
+/// Derivational patterns by length. Hamza is written as ء, as in normalized
+/// words.
+const PATTERNS: [&str; 56] = [
+    // 4 letters; the verb prefixes come first so يقول reads as ق-و-ل
+    "فاعل",
+    "فعال",
+    "يفعل",
+    "تفعل",
+    "نفعل",
+    "ءفعل",
+    "افعل",
+    "مفعل",
+    "فعول",
+    "فعيل",
+    "فعلى",
+    "فعلة",
+    "فعلا",
+    "فعلي",
+    // 5 letters
+    "مفعول",
+    "مفاعل",
+    "مفعال",
+    "تفاعل",
+    "يفاعل",
+    "نفاعل",
+    "افتعل",
+    "انفعل",
+    "تفعيل",
+    "فعالة",
+    "فعيلة",
+    "مفعلة",
+    "فواعل",
+    "فعائل",
+    "ءفعال",
+    "افعال",
+    "فعلان",
+    "فاعلة",
+    "يفتعل",
+    "تفتعل",
+    "مفتعل",
+    "ينفعل",
+    "فعالي",
+    "فعلاء",
+    "فعولة",
+    // 6 letters
+    "مستفعل",
+    "استفعل",
+    "افتعال",
+    "انفعال",
+    "متفاعل",
+    "مفاعيل",
+    "تفاعيل",
+    "يستفعل",
+    "تستفعل",
+    "نستفعل",
+    "مفتعلة",
+    "فعالين",
+    "مفعولة",
+    // 7 letters
+    "استفعال",
+    "مستفعلة",
+    "متفاعلة",
+    "مستفعلي",
+];
+
+/// Suffixes peeled off when no pattern fits the whole word, longest first.
+const SUFFIXES: [&str; 15] = [
+    "تما", "ات", "ون", "ين", "ان", "وا", "تم", "تن", "نا", "ية", "ة", "ه", "ي", "ت", "ا",
+];
+
+/// Words without a meaningful root.
+const ROOTLESS: [&str; 40] = [
+    "الله",
+    "اللهم",
+    "من",
+    "في",
+    "إن",
+    "أن",
+    "ما",
+    "لا",
+    "على",
+    "علي",
+    "إلى",
+    "إلي",
+    "عن",
+    "إلا",
+    "ثم",
+    "أو",
+    "أم",
+    "هو",
+    "هي",
+    "هم",
+    "ذلك",
+    "تلك",
+    "ذا",
+    "هذا",
+    "هذه",
+    "هؤلاء",
+    "أولئك",
+    "الذي",
+    "الذين",
+    "التي",
+    "إذا",
+    "إذ",
+    "لم",
+    "لن",
+    "إيا",
+    "بما",
+    "مما",
+    "عما",
+    "فيما",
+    "كما",
+];
+
+/// A root, usually of three letters, e.g. ك-ت-ب.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Root(String);
+
+impl Root {
+    pub fn letters(&self) -> impl Iterator<Item = char> + '_ {
+        self.0.chars()
+    }
+
+    /// The root letters run together, e.g. "كتب".
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Root {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let letters: Vec<String> = self.0.chars().map(String::from).collect();
+        write!(f, "{}", letters.join("-"))
+    }
+}
+
+impl FromStr for Root {
+    type Err = String;
+
+    /// Accepts "ك-ت-ب", "ك ت ب" or "كتب".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let letters: String = s
+            .chars()
+            .filter(|c| !matches!(c, '-' | ' ' | '_'))
+            .map(normalize_letter)
+            .collect();
+        match letters.chars().count() {
+            2..=4 => Ok(Self(letters)),
+            _ => Err(format!("`{}` is not a root", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RootExtractor {
+    tokenizer: Tokenizer,
+}
+
+impl RootExtractor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Extracts the root of a single word, diacritized or not.
+    pub fn extract(&self, word: &str) -> Option<Root> {
+        let plain_stem = self.tokenizer.stem(word)?;
+        if ROOTLESS.contains(&plain_stem.as_str()) {
+            return None;
+        }
+
+        let spelled = spell_dagger_alef(word);
+        let token = self.tokenizer.tokenize(&spelled).into_iter().next()?;
+        let stem = normalize(&token.stem);
+
+        // A final letter taken for a pronoun may belong to the word (مَٰلِكِ),
+        // so give the patterns a chance at the unsplit form first.
+        if !token.enclitics.is_empty() {
+            let unsplit = normalize(&(token.stem.clone() + &token.enclitics.concat()));
+            if let Some(root) = match_pattern(strip_ta_marbuta(&unsplit)) {
+                return Some(root);
+            }
+        }
+
+        let mut core = strip_ta_marbuta(&stem);
+        let mut quadrilateral = None;
+
+        loop {
+            match core.len() {
+                3 => return Some(trilateral(core)),
+                4..=7 => {
+                    if let Some(root) = match_pattern(core) {
+                        return Some(root);
+                    }
+                    if core.len() == 4 && quadrilateral.is_none() {
+                        quadrilateral = Some(Root(core.iter().collect()));
+                    }
+                }
+                0..=2 => break,
+                _ => {}
+            }
+
+            match strip_suffix(core) {
+                Some(rest) => core = rest,
+                None => break,
+            }
+        }
+
+        if let Some(root) = quadrilateral {
+            return Some(root);
+        }
+
+        match core {
+            // A doubled final letter (رَبِّ) or a dropped middle vowel (قُلۡ).
+            [first, second] if word.contains(SHADDA) => {
+                Some(Root([*first, *second, *second].iter().collect()))
+            }
+            [first, second] => Some(trilateral(&[*first, 'و', *second])),
+            _ => None,
+        }
+    }
+}
+
+/// Extracts the root of `word` with the default extractor.
+pub fn root(word: &str) -> Option<Root> {
+    RootExtractor::new().extract(word)
+}
+
+fn match_pattern(core: &[char]) -> Option<Root> {
+    PATTERNS
+        .iter()
+        .filter(|pattern| pattern.chars().count() == core.len())
+        .find_map(|pattern| {
+            let mut root = ['\0'; 3];
+            for (p, &c) in pattern.chars().zip(core) {
+                match p {
+                    'ف' => root[0] = c,
+                    'ع' => root[1] = c,
+                    'ل' => root[2] = c,
+                    p if p == c => {}
+                    _ => return None,
+                }
+            }
+            Some(trilateral(&root))
+        })
+}
+
+/// Ta marbuta only ever ends a word, so it never belongs to the root.
+fn strip_ta_marbuta(core: &[char]) -> &[char] {
+    match core {
+        [rest @ .., 'ة'] if rest.len() >= 3 => rest,
+        _ => core,
+    }
+}
+
+fn strip_suffix(core: &[char]) -> Option<&[char]> {
+    SUFFIXES.iter().find_map(|suffix| {
+        let suffix: Vec<char> = suffix.chars().collect();
+        (core.len() >= suffix.len() + 2 && core.ends_with(&suffix))
+            .then(|| &core[..core.len() - suffix.len()])
+    })
+}
+
+/// Normalizes three root letters: hamza seats become ء, a long vowel in the
+/// middle becomes و and one at the end becomes ي or و.
+fn trilateral(core: &[char]) -> Root {
+    let mut letters: Vec<char> = core.iter().copied().map(normalize_letter).collect();
+    if letters[0] == 'ا' {
+        letters[0] = 'ء';
+    }
+    if letters[1] == 'ا' {
+        letters[1] = 'و';
+    }
+    letters[2] = match letters[2] {
+        'ى' => 'ي',
+        'ا' => 'و',
+        c => c,
+    };
+    Root(letters.into_iter().collect())
+}
+
+fn normalize_letter(c: char) -> char {
+    match c {
+        'أ' | 'إ' | 'ؤ' | 'ئ' => 'ء',
+        'ٱ' => 'ا',
+        c => c,
+    }
+}
+
+/// Normalizes hamza seats, reading آ as a hamza followed by a long alef.
+fn normalize(stem: &str) -> Vec<char> {
+    stem.chars()
+        .flat_map(|c| match c {
+            'آ' => vec!['ء', 'ا'],
+            c => vec![normalize_letter(c)],
+        })
+        .collect()
+}
+
+/// Writes the dagger alef of the Uthmani script as a full alef so that
+/// patterns see the long vowel (ٱلۡكِتَٰبُ → الكتاب), except where it only
+/// colours an alef maqsura (عَلَىٰ).
+fn spell_dagger_alef(word: &str) -> String {
+    let mut spelled = String::with_capacity(word.len());
+    let mut previous = None;
+
+    for c in word.chars() {
+        if c == DAGGER_ALEF {
+            if !matches!(previous, Some('ى') | Some('ي')) {
+                spelled.push('ا');
+            }
+        } else {
+            spelled.push(c);
+            if c.is_alphabetic() && !is_mark(c) {
+                previous = Some(c);
+            }
+        }
+    }
+
+    spelled
+}
+
+fn is_mark(c: char) -> bool {
+    matches!(c, '\u{064B}'..='\u{065F}' | '\u{06D6}'..='\u{06ED}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root_of(word: &str) -> String {
+        root(word).map(|root| root.to_string()).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_ktb() {
+        for word in ["كَتَبَ", "ٱلۡكِتَٰبُ", "كِتَٰبٌ", "كَاتِبٞ", "مَكۡتُوبًا", "يَكۡتُبُونَ", "وَٱكۡتُبُوا۟"]
+        {
+            assert_eq!(root_of(word), "ك-ت-ب", "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_weak_and_doubled() {
+        assert_eq!(root_of("قَالَ"), "ق-و-ل");
+        assert_eq!(root_of("يَقُولُ"), "ق-و-ل");
+        assert_eq!(root_of("قُلۡ"), "ق-و-ل");
+        assert_eq!(root_of("رَبِّ"), "ر-ب-ب");
+    }
+
+    #[test]
+    fn test_patterns() {
+        assert_eq!(root_of("ٱلۡعَٰلَمِينَ"), "ع-ل-م");
+        assert_eq!(root_of("يَعۡلَمُونَ"), "ع-ل-م");
+        assert_eq!(root_of("ٱلرَّحۡمَٰنِ"), "ر-ح-م");
+        assert_eq!(root_of("ٱلۡمُسۡتَغۡفِرِينَ"), "غ-ف-ر");
+        assert_eq!(root_of("ءَامَنُوا۟"), "ء-م-ن");
+        assert_eq!(root_of("مَٰلِكِ"), "م-ل-ك");
+        assert_eq!(root_of("وَبِٱلۡأٓخِرَةِ"), "ء-خ-ر");
+    }
+
+    #[test]
+    fn test_rootless() {
+        assert_eq!(root("ٱللَّهِ"), None);
+        assert_eq!(root("فِيهِ"), None);
+        assert_eq!(root("ذَٰلِكَ"), None);
+        assert_eq!(root("إِيَّاكَ"), None);
+    }
+
+    #[test]
+    fn test_parse_root() {
+        assert_eq!("ك-ت-ب".parse::<Root>().unwrap(), Root("كتب".to_string()));
+        assert_eq!("قول".parse::<Root>().unwrap().to_string(), "ق-و-ل");
+        assert!("ك".parse::<Root>().is_err());
+    }
+}
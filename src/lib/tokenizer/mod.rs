@@ -0,0 +1,319 @@
+//! Splits Arabic words into attached particles, a stem and attached pronouns.
+//!
+//! This is a light, rule-based segmenter in the spirit of the Light10 stemmer:
+//! it strips at most one conjunction, one preposition or future particle, the
+//! definite article and one pronoun suffix, and refuses any split that would
+//! leave a stem too short to be a word. Stems are returned without marks.
+
+use std::ops::Range;
+
+const SHADDA: char = '\u{0651}';
+
+/// Words that look segmentable but are single lexemes.
+const UNSEGMENTED: [&str; 24] = [
+    "الله",
+    "اللهم",
+    "الذي",
+    "الذين",
+    "التي",
+    "اللذان",
+    "اللتان",
+    "اللاتي",
+    "اللائي",
+    "أولئك",
+    "هنالك",
+    "كذلك",
+    "تلك",
+    "لكن",
+    "لكم",
+    "لعل",
+    "لعلكم",
+    "لعلهم",
+    "لولا",
+    "لما",
+    "لقد",
+    "كان",
+    "كانوا",
+    "فرعون",
+];
+
+/// Particles short enough to fall under the length limits, which still take
+/// a conjunction in front (ولا، فما) or a pronoun behind (فيه، منه).
+const PARTICLES: [&str; 16] = [
+    "لا", "ما", "من", "لم", "لن", "هو", "هي", "هم", "إن", "أن", "في", "عن", "إذ", "إذا", "قد", "لو",
+];
+
+/// Pronoun suffixes, longest first, with the shortest stem each may leave.
+const ENCLITICS: [(&str, usize); 11] = [
+    ("هما", 2),
+    ("كما", 2),
+    ("هم", 2),
+    ("هن", 2),
+    ("كم", 2),
+    ("كن", 2),
+    ("نا", 2),
+    ("ها", 2),
+    ("ه", 3),
+    ("ك", 3),
+    ("ي", 3),
+];
+
+/// One whitespace-separated word of a text, segmented.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token<'a> {
+    /// The word exactly as it appears in the text, marks included.
+    pub surface: &'a str,
+    /// Byte range of `surface` in the text it was read from.
+    pub span: Range<usize>,
+    /// Zero-based word position in the text.
+    pub position: usize,
+    /// Attached particles in reading order, e.g. `["و", "ال"]`.
+    pub proclitics: Vec<String>,
+    pub stem: String,
+    /// Attached pronouns, e.g. `["هم"]`.
+    pub enclitics: Vec<String>,
+}
+
+/// A base letter of a word, noting whether it is doubled.
+#[derive(Debug, Clone, Copy)]
+struct Letter {
+    ch: char,
+    shadda: bool,
+}
+
+impl Letter {
+    fn plain(ch: char) -> Self {
+        Self { ch, shadda: false }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tokenizer;
+
+impl Tokenizer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Splits `text` on whitespace and segments every word. Words made only of
+    /// marks or symbols (such as ۞) produce no token.
+    pub fn tokenize<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        let mut tokens = Vec::new();
+        let mut position = 0;
+
+        for (start, surface) in words_with_offsets(text) {
+            if let Some((proclitics, stem, enclitics)) = self.segment(surface) {
+                tokens.push(Token {
+                    surface,
+                    span: start..start + surface.len(),
+                    position,
+                    proclitics,
+                    stem,
+                    enclitics,
+                });
+                position += 1;
+            }
+        }
+
+        tokens
+    }
+
+    /// Returns the stem of a single word, or `None` if it has no letters.
+    pub fn stem(&self, word: &str) -> Option<String> {
+        self.segment(word).map(|(_, stem, _)| stem)
+    }
+
+    fn segment(&self, word: &str) -> Option<(Vec<String>, String, Vec<String>)> {
+        let mut letters = letters(word);
+        if letters.is_empty() {
+            return None;
+        }
+
+        let mut proclitics = Vec::new();
+        let mut enclitics = Vec::new();
+
+        if !is_unsegmented(&letters) {
+            // Conjunction. Fa is only split off longer words, since many
+            // common words start with it (فيها، فرح).
+            let conjunction_len = match letters[0].ch {
+                'و' => 4,
+                'ف' => 5,
+                _ => usize::MAX,
+            };
+            if letters.len() >= conjunction_len
+                || (conjunction_len != usize::MAX && is_particle(&letters[1..]))
+            {
+                proclitics.push(letters[0].ch.to_string());
+                letters.remove(0);
+            }
+
+            if letters.len() >= 3 && letters[0].ch == 'ل' && letters[1].ch == 'ل' {
+                // لِ + ال: the article's alef is dropped, and when the stem itself
+                // starts with lam the two lams merge under a shadda (لِلَّهِ).
+                proclitics.push("ل".to_string());
+                letters.remove(0);
+                letters.insert(0, Letter::plain('ا'));
+                if letters[1].shadda {
+                    letters.insert(1, Letter::plain('ل'));
+                }
+            } else {
+                let takes_particle = match letters[0].ch {
+                    'ب' | 'ل' => letters.len() >= 5,
+                    // Kaf only as كال, since so many words start with it.
+                    'ك' => letters.len() >= 5 && letters[1].ch == 'ا' && letters[2].ch == 'ل',
+                    'س' => letters.len() >= 5 && matches!(letters[1].ch, 'ي' | 'ت' | 'ن' | 'أ'),
+                    _ => false,
+                };
+                if takes_particle && !is_unsegmented(&letters) {
+                    proclitics.push(letters[0].ch.to_string());
+                    letters.remove(0);
+                }
+            }
+
+            if !is_unsegmented(&letters) {
+                if letters.len() >= 4 && letters[0].ch == 'ا' && letters[1].ch == 'ل' {
+                    proclitics.push("ال".to_string());
+                    letters.drain(..2);
+                } else {
+                    // Definite nouns do not take pronoun suffixes.
+                    for (suffix, min_stem) in ENCLITICS {
+                        let suffix_len = suffix.chars().count();
+                        if letters.len() < suffix_len + min_stem
+                            && !is_particle(&letters[..letters.len().saturating_sub(suffix_len)])
+                        {
+                            continue;
+                        }
+                        let tail = spell(&letters[letters.len() - suffix_len..]);
+                        if tail == suffix {
+                            letters.truncate(letters.len() - suffix_len);
+                            enclitics.push(suffix.to_string());
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        let stem = spell(&letters);
+        Some((proclitics, stem, enclitics))
+    }
+}
+
+/// Yields each whitespace-separated word with its byte offset.
+fn words_with_offsets(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split_whitespace()
+        .map(move |word| (word.as_ptr() as usize - text.as_ptr() as usize, word))
+}
+
+/// Reads the base letters of a word, folding alef wasla into alef and noting
+/// which letters carry a shadda. Harakat and Quranic marks are dropped.
+fn letters(word: &str) -> Vec<Letter> {
+    let mut letters: Vec<Letter> = Vec::new();
+
+    for c in word.chars() {
+        if c == SHADDA {
+            if let Some(last) = letters.last_mut() {
+                last.shadda = true;
+            }
+        } else if is_letter(c) {
+            let ch = if c == 'ٱ' { 'ا' } else { c };
+            letters.push(Letter::plain(ch));
+        }
+    }
+
+    letters
+}
+
+fn is_letter(c: char) -> bool {
+    matches!(c, '\u{0621}'..='\u{063A}' | '\u{0641}'..='\u{064A}' | 'ٱ')
+        || (!is_arabic_block(c) && c.is_alphanumeric())
+}
+
+fn is_arabic_block(c: char) -> bool {
+    matches!(c, '\u{0600}'..='\u{06FF}')
+}
+
+fn is_unsegmented(letters: &[Letter]) -> bool {
+    UNSEGMENTED.contains(&spell(letters).as_str())
+}
+
+fn is_particle(letters: &[Letter]) -> bool {
+    PARTICLES.contains(&spell(letters).as_str())
+}
+
+fn spell(letters: &[Letter]) -> String {
+    letters.iter().map(|letter| letter.ch).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(word: &str) -> (Vec<String>, String, Vec<String>) {
+        Tokenizer::new().segment(word).unwrap()
+    }
+
+    #[test]
+    fn test_article_and_conjunction() {
+        let (proclitics, stem, enclitics) = segment("وَٱلۡأَرۡضِ");
+        assert_eq!(proclitics, vec!["و", "ال"]);
+        assert_eq!(stem, "أرض");
+        assert!(enclitics.is_empty());
+
+        assert_eq!(segment("ٱلۡأَرۡضِ").1, "أرض");
+    }
+
+    #[test]
+    fn test_lillah() {
+        let (proclitics, stem, _) = segment("لِلَّهِ");
+        assert_eq!(proclitics, vec!["ل"]);
+        assert_eq!(stem, "الله");
+        assert_eq!(segment("ٱللَّهِ").1, "الله");
+        assert_eq!(segment("بِٱللَّهِ").1, "الله");
+
+        let (proclitics, stem, _) = segment("لِلنَّاسِ");
+        assert_eq!(proclitics, vec!["ل", "ال"]);
+        assert_eq!(stem, "ناس");
+    }
+
+    #[test]
+    fn test_enclitics() {
+        let (_, stem, enclitics) = segment("رَبِّهِمۡ");
+        assert_eq!(stem, "رب");
+        assert_eq!(enclitics, vec!["هم"]);
+
+        let (proclitics, stem, enclitics) = segment("فِيهَا");
+        assert!(proclitics.is_empty());
+        assert_eq!(stem, "في");
+        assert_eq!(enclitics, vec!["ها"]);
+
+        assert_eq!(segment("فِيهِۛ").1, "في");
+    }
+
+    #[test]
+    fn test_particles() {
+        let (proclitics, stem, _) = segment("وَلَا");
+        assert_eq!(proclitics, vec!["و"]);
+        assert_eq!(stem, "لا");
+        assert_eq!(segment("ٱلَّذِينَ").1, "الذين");
+    }
+
+    #[test]
+    fn test_short_words_are_kept() {
+        assert_eq!(segment("وَعۡدَ").1, "وعد");
+        assert_eq!(segment("كَانُوا۟").1, "كانوا");
+        assert_eq!(segment("إِلَٰهٌ").1, "إله");
+    }
+
+    #[test]
+    fn test_tokenize_spans() {
+        let text = "ٱلۡحَمۡدُ لِلَّهِ رَبِّ ٱلۡعَٰلَمِينَ";
+        let tokens = Tokenizer::new().tokenize(text);
+
+        assert_eq!(tokens.len(), 4);
+        assert_eq!(tokens[1].surface, "لِلَّهِ");
+        assert_eq!(&text[tokens[1].span.clone()], "لِلَّهِ");
+        assert_eq!(tokens[3].position, 3);
+        assert_eq!(tokens[3].stem, "علمين");
+    }
+}
@@ -1,5 +1,17 @@
+//! Letter and word counts shared by [`Ayah`](crate::quran::verse::Ayah) and
+//! [`Surah`](crate::quran::surah::Surah). Bring a trait into scope to call its
+//! methods.
+
+use crate::letters::LetterPolicy;
+
 pub trait TotalLetters {
-    fn total_letters(&self) -> usize;
+    /// Number of letters under the default [`LetterPolicy`].
+    fn total_letters(&self) -> usize {
+        self.total_letters_with(&LetterPolicy::default())
+    }
+
+    /// Number of letters under `policy`.
+    fn total_letters_with(&self, policy: &LetterPolicy) -> usize;
 }
 
 pub trait TotalWords {
@@ -1,3 +1,5 @@
+use crate::normalizer::default_normalizer;
+
 const FATHATAN: char = '\u{064B}';
 const DAMMATAN: char = '\u{064C}';
 const KASRATAN: char = '\u{064D}';
@@ -24,43 +26,17 @@ fn strip_tashkeel(text: &str) -> String {
 
 #[allow(unused)]
 fn is_vocalized(word: &str) -> bool {
-    word.chars().any(|c| is_tashkeel(c))
+    word.chars().any(is_tashkeel)
 }
 
 fn is_tashkeel(archar: char) -> bool {
     TASHKEEL.contains(&archar)
 }
 
-
-fn replace_character(input: &str, search_char: char, replace_char: char) -> String {
-    let mut output = String::new();
-    for c in input.chars() {
-        if c == search_char {
-            output.push(replace_char);
-        } else {
-            output.push(c);
-        }
-    }
-    output
-}
-
+/// Drops harakat, Quranic marks, Arabic-Indic digits and punctuation, and
+/// writes alef wasla as ا. See [`Normalizer`](crate::normalizer::Normalizer) for finer control.
 pub fn remove_diacritics(input: &str) -> String {
-    let input = replace_character(input, 'ٱ', 'ا');
-    let arabic_chars: Vec<char> = vec![
-        'َ', 'ً', 'ُ', 'ٌ', 'ِ', 'ٍ', 'ْ', 'ّ', 'ٰ', 'ٓ', 'ٔ', 'ٕ', 'ٰ', 'ٖ', 'ٗ', '٘', 'ٙ', 'ٚ', 'ٛ', 'ٰ', 'ٔ', 'ٕ', 'ۢ',
-        'ۚ', 'ۖ', 'ۗ', 'ۘ', 'ۙ', 'ۖ', 'ۗ', 'ۚ', 'ۛ', 'ۜ', '۟', '۠', 'ۡ', 'ۢ', 'ۣ', 'ۤ', 'ۥ', 'ۦ', 'ۧ', 'ۨ', 'ۨ', '۪',
-        '۫', '۬', 'ۭ', 'ۮ', 'ۯ', '۰', '۱', '۲', '۳', '۴', '۵', '۶', '۷', '۸', '۹', '٠', '١', '٢', '٣',
-        '٤', '٥', '٦', '٧', '٨', '٩', '؟', '،', '؛',
-    ];
-
-    let mut output = String::new();
-    for c in input.chars() {
-        if !arabic_chars.contains(&c) {
-            output.push(c);
-        }
-    }
-
-    output
+    default_normalizer().normalize(input)
 }
 
 #[cfg(test)]
@@ -90,22 +66,22 @@ mod tests {
         // Test case with vocalized word
         let word = "مَرْحَبًا";
         let vocalized = is_vocalized(word);
-        assert_eq!(vocalized, true);
+        assert!(vocalized);
 
         // Test case with non-vocalized word
         let word = "سلام";
         let vocalized = is_vocalized(word);
-        assert_eq!(vocalized, false);
+        assert!(!vocalized);
     }
 
     #[test]
     fn test_is_tashkeel() {
         // Test case with tashkeel mark
         let tashkeel = 'َ';
-        assert_eq!(is_tashkeel(tashkeel), true);
+        assert!(is_tashkeel(tashkeel));
 
         // Test case with non-tashkeel character
         let non_tashkeel = 'م';
-        assert_eq!(is_tashkeel(non_tashkeel), false);
+        assert!(!is_tashkeel(non_tashkeel));
     }
 }
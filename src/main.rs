@@ -1,8 +1,48 @@
-use quran::quran::{concordance::Concordance, Quran};
+use std::path::PathBuf;
+
+use quran::{
+    normalizer::Normalizer,
+    quran::{
+        concordance::{Concordance, ConcordanceKey},
+        layer::TextLayer,
+        source::{CorpusSource, CsvSource, JsonSource, TanzilTextSource, TanzilXmlSource},
+        Quran,
+    },
+};
 use structopt::StructOpt;
 
 #[derive(StructOpt)]
 struct Options {
+    /// Path to a quran.json-style file to analyze instead of the bundled text
+    #[structopt(short = "d", long = "data", parse(from_os_str))]
+    data_file: Option<PathBuf>,
+
+    /// Format of the --data file
+    #[structopt(
+        short = "f",
+        long = "format",
+        default_value = "json",
+        possible_values = &["json", "tanzil", "tanzil-xml", "csv"]
+    )]
+    format: String,
+
+    /// Text layer to build the concordance from (uthmani, simple-clean, ...)
+    #[structopt(short = "l", long = "layer", default_value = "uthmani")]
+    layer: TextLayer,
+
+    /// What to group the concordance by
+    #[structopt(
+        short = "k",
+        long = "key",
+        default_value = "surface",
+        possible_values = &["surface", "stem", "root"]
+    )]
+    key: String,
+
+    /// Fold marks and letter variants (أ إ آ ٱ, ى, ة, hamza seats) in concordance keys
+    #[structopt(short = "n", long = "normalize")]
+    normalize: bool,
+
     #[structopt(short = "s", long = "surah")]
     surah_number: Option<usize>,
 
@@ -19,14 +59,35 @@ struct Options {
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let options = Options::from_args();
 
-    let quran = Quran::new()?;
-    let mut concordance = Concordance::new(&quran);
+    let quran = match &options.data_file {
+        Some(path) => {
+            let source: Box<dyn CorpusSource> = match options.format.as_str() {
+                "tanzil" => Box::new(TanzilTextSource),
+                "tanzil-xml" => Box::new(TanzilXmlSource),
+                "csv" => Box::new(CsvSource::default()),
+                _ => Box::new(JsonSource),
+            };
+            Quran::from_source_path(source.as_ref(), path)?
+        }
+        None => Quran::new()?,
+    };
+    let key = match options.key.as_str() {
+        "stem" => ConcordanceKey::Stem,
+        "root" => ConcordanceKey::Root,
+        _ => ConcordanceKey::Surface,
+    };
+    let mut concordance = Concordance::new(&quran)
+        .with_layer(options.layer)?
+        .key_by(key);
+    if options.normalize {
+        concordance = concordance.with_normalizer(Normalizer::full());
+    }
 
-    concordance.generate(
+    concordance.try_generate(
         options.surah_number,
         options.start_ayah_number,
         options.end_ayah_number,
-    );
+    )?;
     concordance.print_to_file(&options.output_file);
 
     Ok(())